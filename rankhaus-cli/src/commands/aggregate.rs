@@ -0,0 +1,234 @@
+use crate::state::AppState;
+use crate::{AggregateArgs, AggregateMethod};
+use anyhow::{bail, Result};
+use rankhaus::aggregate::{self, Method, Selection, TieBreaker};
+use std::collections::HashSet;
+
+pub fn execute(args: AggregateArgs, state: Option<&mut AppState>) -> Result<()> {
+    let rankset = state.and_then(|s| s.rankset.as_ref()).ok_or_else(|| {
+        anyhow::anyhow!("No rankset loaded. Use 'init <name>' or 'load <file>' first.")
+    })?;
+
+    let method = match args.method {
+        AggregateMethod::Borda => Method::Borda,
+        AggregateMethod::Copeland => Method::Copeland,
+        AggregateMethod::AverageRank => Method::AverageRank,
+        AggregateMethod::Kemeny => Method::Kemeny,
+    };
+    let selection = Selection {
+        user: match &args.user {
+            Some(identifier) => Some(rankset.get_user(identifier)?.id.clone()),
+            None => None,
+        },
+        strategy: args.strategy.clone(),
+        criterion: match &args.criterion {
+            Some(name) => Some(rankset.get_criterion(name)?.name.clone()),
+            None => None,
+        },
+    };
+
+    let rankings = selection.rankings(rankset);
+    if rankings.is_empty() {
+        bail!("No completed rankings match. Finish a ranking with 'rank' first.");
+    }
+    let users: HashSet<_> = rankings.iter().map(|r| &r.user_id).collect();
+    let result = aggregate::aggregate(rankset, method, &selection)?;
+
+    let value = |id: &rankhaus::Id| {
+        rankset
+            .get_item(id.as_str())
+            .map(|item| item.value.clone())
+            .unwrap_or_else(|_| id.to_string())
+    };
+    let score = |score: f64| match method {
+        Method::AverageRank => format!("{:.2}", score),
+        _ => score.to_string(),
+    };
+
+    let mut scope = vec![format!(
+        "{} ranking{} by {} user{}",
+        result.rankings,
+        if result.rankings == 1 { "" } else { "s" },
+        users.len(),
+        if users.len() == 1 { "" } else { "s" }
+    )];
+    if let Some(criterion) = &selection.criterion {
+        scope.push(format!("criterion: {}", criterion));
+    }
+    if let Some(strategy) = &selection.strategy {
+        scope.push(format!("strategy: {}", strategy));
+    }
+
+    println!();
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("  Group Ranking · {}", method);
+    println!("  {}", scope.join(" · "));
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("  Score: {}", method.describe());
+    println!();
+
+    for (rank, standing) in result.standings.iter().enumerate() {
+        println!(
+            "  {:>2}. {:<30} {:>6}   (avg rank {:.2}, in {} of {})",
+            rank + 1,
+            value(&standing.item),
+            score(standing.score),
+            standing.average_rank,
+            standing.rankings,
+            result.rankings
+        );
+    }
+
+    println!();
+    let note = if result.optimal {
+        " (fewest possible)".to_string()
+    } else if method == Method::Kemeny {
+        format!(
+            " (found by local search; only up to {} items are solved exactly)",
+            aggregate::KEMENY_EXACT_LIMIT
+        )
+    } else {
+        String::new()
+    };
+    println!(
+        "  Disagreement: {} pair{} placed the other way round by a ranking{}",
+        result.disagreement,
+        if result.disagreement == 1 { "" } else { "s" },
+        note
+    );
+
+    if !result.tie_breaks.is_empty() {
+        println!();
+        println!("  Ties broken:");
+        for tie in &result.tie_breaks {
+            let reason = match tie.by {
+                TieBreaker::AverageRank => "better average rank",
+                TieBreaker::Id => "level on average rank too, so by ID",
+            };
+            let level = match method {
+                Method::Kemeny => {
+                    let each_way = tie.score as usize;
+                    format!(
+                        "{} ranking{} each way",
+                        each_way,
+                        if each_way == 1 { "" } else { "s" }
+                    )
+                }
+                _ => format!("both {}", score(tie.score)),
+            };
+            println!(
+                "    {} above {} ({}): {}",
+                value(&tie.above),
+                value(&tie.below),
+                level,
+                reason
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankhaus::{Id, Item, RankResult, RankSet, Ranking, User};
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(|n| Id::from(*n)).collect()
+    }
+
+    /// A completed "merge" ranking by `user_id` with the given order, best
+    /// first
+    fn completed_ranking(user_id: &Id, order: &[Id]) -> Ranking {
+        let mut ranking = Ranking::new(user_id.clone(), "merge".to_string());
+        ranking.result = Some(RankResult {
+            order: Some(order.to_vec()),
+            ..Default::default()
+        });
+        ranking.session.info.complete();
+        ranking
+    }
+
+    fn create_test_state() -> AppState {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        for name in ["a", "b", "c"] {
+            let mut item = Item::new(name.to_string());
+            item.id = Id::from(name);
+            rankset.add_item(item).unwrap();
+        }
+        for (name, order) in [("alice", ["a", "b", "c"]), ("bob", ["b", "a", "c"])] {
+            let user = User::new(name.to_string(), None);
+            rankset
+                .rankings
+                .push(completed_ranking(&user.id, &ids(&order)));
+            rankset.add_user(user).unwrap();
+        }
+
+        AppState {
+            rankset: Some(rankset),
+            active_user_id: None,
+            active_strategy: "merge".to_string(),
+            active_strategy_config: None,
+        }
+    }
+
+    fn args(method: AggregateMethod) -> AggregateArgs {
+        AggregateArgs {
+            method,
+            user: None,
+            strategy: None,
+            criterion: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut state = create_test_state();
+        for method in [
+            AggregateMethod::Borda,
+            AggregateMethod::Copeland,
+            AggregateMethod::AverageRank,
+            AggregateMethod::Kemeny,
+        ] {
+            assert!(execute(args(method), Some(&mut state)).is_ok());
+        }
+
+        let mut by_user = args(AggregateMethod::Borda);
+        by_user.user = Some("alice".to_string());
+        assert!(execute(by_user, Some(&mut state)).is_ok());
+
+        let mut unknown = args(AggregateMethod::Borda);
+        unknown.user = Some("nobody".to_string());
+        assert!(execute(unknown, Some(&mut state)).is_err());
+    }
+
+    #[test]
+    fn test_aggregate_skips_subsets_and_stopped_rankings() {
+        let mut state = create_test_state();
+        let rankset = state.rankset.as_mut().unwrap();
+        for ranking in &mut rankset.rankings {
+            ranking.result.as_mut().unwrap().confidence = Some(0.5);
+        }
+        let user_id = rankset.get_user("alice").unwrap().id.clone();
+        let mut subset = completed_ranking(&user_id, &ids(&["a", "b"]));
+        subset.filter = Some(rankhaus::filter::ItemFilter::new(
+            vec![],
+            Default::default(),
+            ids(&["a", "b"]),
+        ));
+        rankset.rankings.push(subset);
+
+        let result = execute(args(AggregateMethod::Borda), Some(&mut state));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("No completed rankings match"));
+    }
+
+    #[test]
+    fn test_aggregate_no_state() {
+        assert!(execute(args(AggregateMethod::Borda), None).is_err());
+    }
+}
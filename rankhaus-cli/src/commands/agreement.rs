@@ -0,0 +1,107 @@
+use crate::state::AppState;
+use crate::AgreementArgs;
+use anyhow::{bail, Result};
+use rankhaus::agreement;
+use rankhaus::{Id, RankSet};
+use serde_json::json;
+
+pub fn execute(args: AgreementArgs, state: Option<&mut AppState>) -> Result<()> {
+    let rankset = state.and_then(|s| s.rankset.as_ref()).ok_or_else(|| {
+        anyhow::anyhow!("No rankset loaded. Use 'init <name>' or 'load <file>' first.")
+    })?;
+
+    if !(-1.0..=1.0).contains(&args.min_tau) {
+        bail!("--min-tau must be between -1 and 1");
+    }
+    let criterion = match &args.criterion {
+        Some(name) => Some(rankset.get_criterion(name)?.name.as_str()),
+        None => None,
+    };
+
+    let result = agreement::agreement(rankset, criterion, args.min_tau)?;
+    let name = |id: &Id| username(rankset, id);
+    let without_ranking: Vec<String> = rankset
+        .users
+        .values()
+        .filter(|u| !result.users.contains(&u.id))
+        .map(|u| u.username.clone())
+        .collect();
+
+    if args.json {
+        let output = json!({
+            "criterion": criterion,
+            "min_tau": args.min_tau,
+            "users": result.users.iter().map(|id| json!({
+                "id": id,
+                "username": name(id),
+            })).collect::<Vec<_>>(),
+            "kendall_tau": result.kendall_tau,
+            "clusters": result.clusters.iter().map(|c| json!({
+                "users": c.users.iter().map(name).collect::<Vec<_>>(),
+                "cohesion": c.cohesion,
+            })).collect::<Vec<_>>(),
+            "without_ranking": without_ranking,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    // Columns are narrow, so long usernames are cut short in the header
+    let short = |id: &Id| name(id).chars().take(7).collect::<String>();
+
+    println!();
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(
+        "  Agreement between {} users (Kendall tau{})",
+        result.users.len(),
+        criterion
+            .map(|c| format!(", criterion: {}", c))
+            .unwrap_or_default()
+    );
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+
+    let header: String = result
+        .users
+        .iter()
+        .map(|id| format!(" {:>7}", short(id)))
+        .collect();
+    println!("  {:<16}{}", "", header);
+    for (id, row) in result.users.iter().zip(&result.kendall_tau) {
+        let cells: String = row.iter().map(|tau| format!(" {:>7.2}", tau)).collect();
+        println!("  {:<16}{}", name(id), cells);
+    }
+
+    println!();
+    println!(
+        "  Taste groups (members agree at tau ≥ {} on average):",
+        args.min_tau
+    );
+    for (i, cluster) in result.clusters.iter().enumerate() {
+        let members: Vec<String> = cluster.users.iter().map(name).collect();
+        let cohesion = if cluster.users.len() < 2 {
+            "on their own".to_string()
+        } else {
+            format!("mean tau {:.2}", cluster.cohesion)
+        };
+        println!("    {}. {}  ({})", i + 1, members.join(", "), cohesion);
+    }
+
+    if !without_ranking.is_empty() {
+        println!();
+        println!(
+            "  No completed ranking of all items: {}",
+            without_ranking.join(", ")
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
+fn username(rankset: &RankSet, id: &Id) -> String {
+    rankset
+        .get_user(id.as_str())
+        .map(|u| u.username.clone())
+        .unwrap_or_else(|_| id.to_string())
+}
@@ -0,0 +1,263 @@
+use crate::state::AppState;
+use crate::CompareArgs;
+use anyhow::{bail, Result};
+use rankhaus::correlation;
+use rankhaus::{Id, RankSet, Ranking};
+
+/// How top-heavy rank-biased overlap is: the top 10 items carry most weight
+const RBO_P: f64 = 0.9;
+
+pub fn execute(args: CompareArgs, state: Option<&mut AppState>) -> Result<()> {
+    let rankset = state.and_then(|s| s.rankset.as_ref()).ok_or_else(|| {
+        anyhow::anyhow!("No rankset loaded. Use 'init <name>' or 'load <file>' first.")
+    })?;
+
+    let criterion = match &args.criterion {
+        Some(name) => Some(rankset.get_criterion(name)?.name.as_str()),
+        None => None,
+    };
+    let first = find_ranking(rankset, &args.first, criterion)?;
+    let second = find_ranking(rankset, &args.second, criterion)?;
+    if first.session.info.id == second.session.info.id {
+        bail!("Both refer to the same ranking. Compare two users or two sessions.");
+    }
+
+    let first_order = order(first);
+    let second_order = order(second);
+    let shared = first_order
+        .iter()
+        .filter(|id| second_order.contains(id))
+        .count();
+    if shared < 2 {
+        bail!("The rankings share fewer than 2 items, so there is nothing to compare.");
+    }
+
+    let first_label = label(rankset, first, second);
+    let second_label = label(rankset, second, first);
+    let value = |id: &Id| {
+        rankset
+            .get_item(id.as_str())
+            .map(|item| item.value.clone())
+            .unwrap_or_else(|_| id.to_string())
+    };
+
+    println!();
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("  Agreement: {} vs {}", first_label, second_label);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    for (label, ranking) in [(&first_label, first), (&second_label, second)] {
+        println!(
+            "  {:<27} {} items, {}, session {}",
+            format!("{}:", label),
+            order(ranking).len(),
+            ranking.strategy,
+            ranking.session.info.id
+        );
+    }
+    println!();
+    println!("  Shared items:        {}", shared);
+    println!(
+        "  Kendall tau:         {:>6.3}",
+        correlation::kendall_tau(first_order, second_order)
+    );
+    println!(
+        "  Spearman rho:        {:>6.3}",
+        correlation::spearman_rho(first_order, second_order)
+    );
+    println!(
+        "  Rank-biased overlap: {:>6.3}  (top-weighted, p = {})",
+        correlation::rank_biased_overlap(first_order, second_order, RBO_P),
+        RBO_P
+    );
+    println!();
+    println!("  Tau and rho run from -1 (reversed) to 1 (identical) over shared items;");
+    println!("  overlap runs from 0 to 1 and counts agreement near the top most.");
+
+    let disputes = correlation::disputed_pairs(first_order, second_order);
+    println!();
+    if disputes.is_empty() {
+        println!("  No disputed pairs: every shared pair is in the same order.");
+    } else {
+        println!(
+            "  Most disputed pairs ({} of {}):",
+            disputes.len().min(args.top),
+            disputes.len()
+        );
+        println!("    {:<36} {}", first_label, second_label);
+        let position = |order: &[Id], id: &Id| order.iter().position(|x| x == id).unwrap() + 1;
+        let above = |order: &[Id], high: &Id, low: &Id| {
+            format!(
+                "{} #{} > {} #{}",
+                value(high),
+                position(order, high),
+                value(low),
+                position(order, low)
+            )
+        };
+        for dispute in disputes.iter().take(args.top) {
+            let (a, b) = (&dispute.first_prefers, &dispute.second_prefers);
+            println!(
+                "    {:<36} {}",
+                above(first_order, a, b),
+                above(second_order, b, a)
+            );
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// A user's latest completed ranking, or a completed session by ID (or
+/// prefix)
+fn find_ranking<'a>(
+    rankset: &'a RankSet,
+    identifier: &str,
+    criterion: Option<&str>,
+) -> Result<&'a Ranking> {
+    if let Ok(user) = rankset.get_user(identifier) {
+        return rankset
+            .latest_ranking(&user.id, criterion, None)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "{} has no completed ranking of all items{}",
+                    user.username,
+                    criterion
+                        .map(|c| format!(" on '{}'", c))
+                        .unwrap_or_default()
+                )
+            });
+    }
+
+    let ranking = rankset
+        .rankings
+        .iter()
+        .find(|r| {
+            let id_str = r.session.info.id.as_str();
+            id_str == identifier || id_str.starts_with(identifier)
+        })
+        .ok_or_else(|| anyhow::anyhow!("No user or session '{}'", identifier))?;
+    if !ranking.is_complete() {
+        bail!(
+            "Session {} is still in progress. Finish it with 'sessions resume {}'.",
+            ranking.session.info.id,
+            ranking.session.info.id
+        );
+    }
+    Ok(ranking)
+}
+
+fn order(ranking: &Ranking) -> &[Id] {
+    ranking
+        .result
+        .as_ref()
+        .and_then(|r| r.order.as_deref())
+        .unwrap_or_default()
+}
+
+/// The ranking's username, with its completion date when the other ranking
+/// is by the same user
+fn label(rankset: &RankSet, ranking: &Ranking, other: &Ranking) -> String {
+    let username = rankset
+        .get_user(ranking.user_id.as_str())
+        .map(|u| u.username.clone())
+        .unwrap_or_else(|_| ranking.user_id.to_string());
+    match ranking.session.info.completed {
+        Some(completed) if ranking.user_id == other.user_id => {
+            format!("{} ({})", username, completed.format("%Y-%m-%d %H:%M"))
+        }
+        _ => username,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankhaus::{Item, RankResult, Ranking, User};
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(|n| Id::from(*n)).collect()
+    }
+
+    /// A completed "merge" ranking by `user_id` with the given order, best
+    /// first
+    fn completed_ranking(user_id: &Id, order: &[Id]) -> Ranking {
+        let mut ranking = Ranking::new(user_id.clone(), "merge".to_string());
+        ranking.result = Some(RankResult {
+            order: Some(order.to_vec()),
+            ..Default::default()
+        });
+        ranking.session.info.complete();
+        ranking
+    }
+
+    fn create_test_state() -> AppState {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        for name in ["a", "b", "c"] {
+            let mut item = Item::new(name.to_string());
+            item.id = Id::from(name);
+            rankset.add_item(item).unwrap();
+        }
+        for (name, order) in [("alice", ["a", "b", "c"]), ("bob", ["c", "a", "b"])] {
+            let user = User::new(name.to_string(), None);
+            rankset
+                .rankings
+                .push(completed_ranking(&user.id, &ids(&order)));
+            rankset.add_user(user).unwrap();
+        }
+
+        AppState {
+            rankset: Some(rankset),
+            active_user_id: None,
+            active_strategy: "merge".to_string(),
+            active_strategy_config: None,
+        }
+    }
+
+    fn args(first: &str, second: &str) -> CompareArgs {
+        CompareArgs {
+            first: first.to_string(),
+            second: second.to_string(),
+            criterion: None,
+            top: 5,
+        }
+    }
+
+    #[test]
+    fn test_compare_users() {
+        let mut state = create_test_state();
+        assert!(execute(args("alice", "bob"), Some(&mut state)).is_ok());
+
+        let session = state.rankset.as_ref().unwrap().rankings[1]
+            .session
+            .info
+            .id
+            .to_string();
+        assert!(execute(args("alice", &session), Some(&mut state)).is_ok());
+    }
+
+    #[test]
+    fn test_compare_same_ranking() {
+        let mut state = create_test_state();
+        let result = execute(args("alice", "alice"), Some(&mut state));
+        assert!(result.unwrap_err().to_string().contains("same ranking"));
+    }
+
+    #[test]
+    fn test_compare_unfinished_or_unknown() {
+        let mut state = create_test_state();
+        let rankset = state.rankset.as_mut().unwrap();
+        let user_id = rankset.get_user("bob").unwrap().id.clone();
+        let in_progress = Ranking::new(user_id, "merge".to_string());
+        let session = in_progress.session.info.id.to_string();
+        rankset.rankings.push(in_progress);
+
+        let result = execute(args("alice", &session), Some(&mut state));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("still in progress"));
+        assert!(execute(args("alice", "nobody"), Some(&mut state)).is_err());
+        assert!(execute(args("alice", "bob"), None).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::state::AppState;
+use crate::CriteriaCommands;
+use anyhow::{bail, Context, Result};
+use rankhaus::criteria::{self, Criterion};
+use rankhaus::Id;
+
+pub fn execute(command: CriteriaCommands, state: Option<&mut AppState>) -> Result<()> {
+    // Check if list is loaded
+    let has_rankset = state.as_ref().map(|s| s.has_rankset()).unwrap_or(false);
+    if !has_rankset {
+        bail!("No rankset loaded. Use 'init <name>' or 'load <file>' first.");
+    }
+
+    match command {
+        CriteriaCommands::List => list(state),
+        CriteriaCommands::Add { name, question } => add(state, name, question),
+        CriteriaCommands::Remove { name } => remove(state, name),
+        CriteriaCommands::Combine { weights, user } => combine(state, weights, user),
+    }
+}
+
+fn list(state: Option<&mut AppState>) -> Result<()> {
+    let rankset = state
+        .and_then(|s| s.rankset.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded"))?;
+
+    if rankset.meta.criteria.is_empty() {
+        println!("No criteria yet. Use 'criteria add <name>' to add some.");
+        return Ok(());
+    }
+
+    println!("\nCriteria ({})", rankset.meta.criteria.len());
+    println!("{:-<60}", "");
+    println!("{:<16} {:<10} Question", "Name", "Rankings");
+    println!("{:-<60}", "");
+
+    for criterion in &rankset.meta.criteria {
+        let rankings = rankset
+            .rankings
+            .iter()
+            .filter(|r| r.criterion.as_deref() == Some(criterion.name.as_str()))
+            .count();
+        println!(
+            "{:<16} {:<10} {}",
+            criterion.name,
+            rankings,
+            criterion.prompt()
+        );
+    }
+
+    println!();
+    Ok(())
+}
+
+fn add(state: Option<&mut AppState>, name: String, question: Option<String>) -> Result<()> {
+    let rankset = state
+        .and_then(|s| s.rankset.as_mut())
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded"))?;
+
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        bail!("Criterion name cannot be empty");
+    }
+
+    let question = question
+        .map(|q| q.trim().to_string())
+        .filter(|q| !q.is_empty());
+    rankset.add_criterion(Criterion::new(name.clone(), question))?;
+
+    // Auto-save
+    rankset.save().context("Failed to save rankset")?;
+
+    println!("✓ Added criterion: {}", name);
+    println!("Rank it with: rank --criterion {}", name);
+    Ok(())
+}
+
+fn remove(state: Option<&mut AppState>, name: String) -> Result<()> {
+    let rankset = state
+        .and_then(|s| s.rankset.as_mut())
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded"))?;
+
+    let removed = rankset.remove_criterion(&name)?;
+
+    // Auto-save
+    rankset.save().context("Failed to save rankset")?;
+
+    println!("✓ Removed criterion: {}", removed.name);
+    Ok(())
+}
+
+/// Blend a user's latest ranking on each criterion into an overall order
+fn combine(state: Option<&mut AppState>, weights: Vec<String>, user: Option<String>) -> Result<()> {
+    let app_state = state.ok_or_else(|| anyhow::anyhow!("No state available"))?;
+    let rankset = app_state
+        .rankset
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded"))?;
+
+    if rankset.meta.criteria.is_empty() {
+        bail!("No criteria defined. Use 'criteria add <name>' first.");
+    }
+
+    let user_id: Id = match (&user, &app_state.active_user_id) {
+        (Some(identifier), _) => rankset.get_user(identifier)?.id.clone(),
+        (None, Some(user_id)) => user_id.clone(),
+        (None, None) => rankset
+            .users
+            .values()
+            .find(|u| u.default)
+            .map(|u| u.id.clone())
+            .ok_or_else(|| {
+                anyhow::anyhow!("No active user. Use --user or 'users select <user>'.")
+            })?,
+    };
+    let username = rankset.get_user(&user_id.to_string())?.username.clone();
+
+    // Every criterion counts equally unless given a weight
+    let mut weighted: Vec<(String, f64)> = rankset
+        .meta
+        .criteria
+        .iter()
+        .map(|c| (c.name.clone(), 1.0))
+        .collect();
+    for weight in &weights {
+        let (name, value) = weight
+            .rsplit_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected CRITERION=WEIGHT, got '{}'", weight))?;
+        let criterion = rankset.get_criterion(name.trim())?;
+        let value: f64 = value
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid weight in '{}'", weight))?;
+        if let Some(entry) = weighted.iter_mut().find(|(n, _)| *n == criterion.name) {
+            entry.1 = value;
+        }
+    }
+
+    let mut orders = Vec::new();
+    for (name, weight) in &weighted {
+        match rankset
+            .latest_ranking(&user_id, Some(name), None)
+            .and_then(|r| r.result.as_ref())
+            .and_then(|r| r.order.as_ref())
+        {
+            Some(order) => orders.push((name, order.as_slice(), *weight)),
+            None => println!("⚠ {} has no completed ranking on '{}'", username, name),
+        }
+    }
+
+    if orders.is_empty() {
+        bail!("No completed rankings to combine. Use 'rank --criterion <name>' first.");
+    }
+
+    let inputs: Vec<(&[Id], f64)> = orders.iter().map(|(_, o, w)| (*o, *w)).collect();
+    let combined = criteria::combine(&inputs)?;
+
+    let total_weight: f64 = orders.iter().map(|(_, _, w)| w).sum();
+    let blend: Vec<String> = orders
+        .iter()
+        .map(|(name, _, w)| format!("{} {:.0}%", name, w / total_weight * 100.0))
+        .collect();
+
+    println!();
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("  Combined Ranking for {}", username);
+    println!("  {}", blend.join(" · "));
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+
+    let mut rank = 0;
+    for (item_id, score) in &combined {
+        if let Ok(item) = rankset.get_item(&item_id.to_string()) {
+            rank += 1;
+            println!("  {}. {} ({:.2})", rank, item.value, score);
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankhaus::{RankSet, User};
+    use std::path::PathBuf;
+
+    fn create_test_state() -> AppState {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        rankset.file_path = Some(PathBuf::from("test_criteria.rankset"));
+
+        let user = User::new("testuser".to_string(), None);
+        rankset.add_user(user).unwrap();
+
+        AppState {
+            rankset: Some(rankset),
+            active_user_id: None,
+            active_strategy: "merge".to_string(),
+            active_strategy_config: None,
+        }
+    }
+
+    #[test]
+    fn test_add_and_remove() {
+        let mut state = create_test_state();
+
+        let result = add(Some(&mut state), "taste".to_string(), None);
+        assert!(result.is_ok());
+        let result = add(Some(&mut state), "taste".to_string(), None);
+        assert!(result.is_err());
+
+        let result = remove(Some(&mut state), "taste".to_string());
+        assert!(result.is_ok());
+        assert!(state.rankset.as_ref().unwrap().meta.criteria.is_empty());
+
+        let _ = std::fs::remove_file("test_criteria.rankset");
+    }
+
+    #[test]
+    fn test_combine_without_rankings() {
+        let mut state = create_test_state();
+        state
+            .rankset
+            .as_mut()
+            .unwrap()
+            .add_criterion(Criterion::new("taste".to_string(), None))
+            .unwrap();
+
+        let result = combine(Some(&mut state), vec![], Some("testuser".to_string()));
+        assert!(result.is_err());
+
+        let result = combine(
+            Some(&mut state),
+            vec!["price=2".to_string()],
+            Some("testuser".to_string()),
+        );
+        assert!(result.is_err());
+    }
+}
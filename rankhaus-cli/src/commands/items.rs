@@ -1,6 +1,7 @@
 use crate::state::AppState;
 use crate::ItemsCommands;
 use anyhow::{bail, Context, Result};
+use rankhaus::item_stats;
 use rankhaus::Item;
 use std::io::{self, BufRead};
 
@@ -19,6 +20,25 @@ pub fn execute(command: ItemsCommands, state: Option<&mut AppState>) -> Result<(
             identifier,
             new_value,
         } => edit(state, identifier, new_value),
+        ItemsCommands::Group { identifier, group } => set_group(state, identifier, group),
+        ItemsCommands::Show { identifier } => show(state, identifier),
+        ItemsCommands::Stats { criterion } => stats(state, criterion),
+        ItemsCommands::Set {
+            identifier,
+            description,
+            url,
+            tags,
+            untags,
+            attributes,
+        } => set(
+            state,
+            identifier,
+            description,
+            url,
+            tags,
+            untags,
+            attributes,
+        ),
     }
 }
 
@@ -38,11 +58,28 @@ fn list(state: Option<&mut AppState>) -> Result<()> {
 
     println!("\nItems ({})", items.len());
     println!("{:-<60}", "");
-    println!("{:<10} Value", "ID");
-    println!("{:-<60}", "");
 
-    for item in items {
-        println!("{:<10} {}", item.id.as_str(), item.value);
+    if items.iter().any(|item| item.group.is_some()) {
+        println!("{:<10} {:<16} Value", "ID", "Group");
+        println!("{:-<60}", "");
+
+        for item in items {
+            println!(
+                "{:<10} {:<16} {}",
+                item.id.as_str(),
+                item.group.as_deref().unwrap_or("-"),
+                item.value
+            );
+            print_details(item, &" ".repeat(28));
+        }
+    } else {
+        println!("{:<10} Value", "ID");
+        println!("{:-<60}", "");
+
+        for item in items {
+            println!("{:<10} {}", item.id.as_str(), item.value);
+            print_details(item, &" ".repeat(11));
+        }
     }
 
     println!();
@@ -224,6 +261,204 @@ fn edit(state: Option<&mut AppState>, identifier: String, new_value: String) ->
     Ok(())
 }
 
+fn set_group(
+    state: Option<&mut AppState>,
+    identifier: String,
+    group: Option<String>,
+) -> Result<()> {
+    let list = state
+        .and_then(|s| s.rankset.as_mut())
+        .ok_or_else(|| anyhow::anyhow!("No list loaded"))?;
+
+    let group = group
+        .map(|g| g.trim().to_string())
+        .filter(|g| !g.is_empty());
+
+    let item = list
+        .get_item_mut(&identifier)
+        .context(format!("Item not found: {}", identifier))?;
+    item.group = group.clone();
+    let value = item.value.clone();
+
+    // Auto-save
+    list.save().context("Failed to save list")?;
+
+    match group {
+        Some(group) => println!("✓ '{}' is now in group '{}'", value, group),
+        None => println!("✓ Cleared group for '{}'", value),
+    }
+
+    Ok(())
+}
+
+fn show(state: Option<&mut AppState>, identifier: String) -> Result<()> {
+    let list = state
+        .and_then(|s| s.rankset.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("No list loaded"))?;
+
+    let item = list
+        .get_item(&identifier)
+        .context(format!("Item not found: {}", identifier))?;
+
+    println!();
+    println!("  {} ({})", item.value, item.id);
+    if let Some(group) = &item.group {
+        println!("  Group: {}", group);
+    }
+    print_details(item, "  ");
+    println!();
+
+    Ok(())
+}
+
+fn set(
+    state: Option<&mut AppState>,
+    identifier: String,
+    description: Option<String>,
+    url: Option<String>,
+    tags: Vec<String>,
+    untags: Vec<String>,
+    attributes: Vec<String>,
+) -> Result<()> {
+    let list = state
+        .and_then(|s| s.rankset.as_mut())
+        .ok_or_else(|| anyhow::anyhow!("No list loaded"))?;
+
+    // Parse everything before touching the item
+    let non_empty = |value: String| {
+        let value = value.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    };
+    let mut parsed_attributes = Vec::new();
+    for attribute in attributes {
+        let (key, value) = attribute
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected KEY=VALUE, got '{}'", attribute))?;
+        let key = key.trim();
+        if key.is_empty() {
+            bail!("Attribute name cannot be empty");
+        }
+        parsed_attributes.push((key.to_string(), non_empty(value.to_string())));
+    }
+
+    let item = list
+        .get_item_mut(&identifier)
+        .context(format!("Item not found: {}", identifier))?;
+
+    if let Some(description) = description {
+        item.description = non_empty(description);
+    }
+    if let Some(url) = url {
+        item.url = non_empty(url);
+    }
+    for tag in tags.into_iter().filter_map(non_empty) {
+        item.add_tag(&tag);
+    }
+    for tag in &untags {
+        item.remove_tag(tag.trim());
+    }
+    for (key, value) in parsed_attributes {
+        match value {
+            Some(value) => {
+                item.attributes.insert(key, value);
+            }
+            None => {
+                item.attributes.remove(&key);
+            }
+        }
+    }
+    let value = item.value.clone();
+
+    // Auto-save
+    list.save().context("Failed to save list")?;
+
+    println!("✓ Updated '{}'", value);
+    Ok(())
+}
+
+/// Print an item's description, link, tags and attributes, one per line
+pub fn print_details(item: &Item, indent: &str) {
+    if let Some(description) = &item.description {
+        println!("{}{}", indent, description);
+    }
+    if let Some(url) = &item.url {
+        println!("{}🔗 {}", indent, url);
+    }
+    if !item.tags.is_empty() {
+        println!("{}🏷  {}", indent, item.tags.join(", "));
+    }
+    for (key, value) in &item.attributes {
+        println!("{}{}: {}", indent, key, value);
+    }
+}
+
+fn stats(state: Option<&mut AppState>, criterion: Option<String>) -> Result<()> {
+    let list = state
+        .and_then(|s| s.rankset.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("No list loaded"))?;
+
+    if list.items.is_empty() {
+        println!("No items yet. Use 'items add' to add some.");
+        return Ok(());
+    }
+    let criterion = match &criterion {
+        Some(name) => Some(list.get_criterion(name)?.name.as_str()),
+        None => None,
+    };
+
+    let stats = item_stats::item_stats(list, criterion);
+    let rankers = stats
+        .iter()
+        .filter_map(|s| s.rank.as_ref())
+        .map(|r| r.count)
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "\nItem statistics{} ({} ranker{})",
+        criterion.map(|c| format!(" for {}", c)).unwrap_or_default(),
+        rankers,
+        if rankers == 1 { "" } else { "s" }
+    );
+    println!("{:-<96}", "");
+    println!(
+        "{:<24} {:>6} {:>7} {:>6} {:>5} {:>6} {:>10} {:>8} {:>12}",
+        "Item", "Mean", "Median", "SD", "Best", "Worst", "W-L", "Win rate", "Controversy"
+    );
+    println!("{:-<96}", "");
+
+    let dash = || "-".to_string();
+    for stat in &stats {
+        let value = list
+            .get_item(stat.item.as_str())
+            .map(|item| item.value.clone())
+            .unwrap_or_else(|_| stat.item.to_string());
+        let rank = stat.rank.as_ref();
+        println!(
+            "{:<24} {:>6} {:>7} {:>6} {:>5} {:>6} {:>10} {:>8} {:>12}",
+            value,
+            rank.map_or_else(dash, |r| format!("{:.1}", r.mean)),
+            rank.map_or_else(dash, |r| format!("{:.1}", r.median)),
+            rank.map_or_else(dash, |r| format!("{:.2}", r.std_dev)),
+            rank.map_or_else(dash, |r| r.best.to_string()),
+            rank.map_or_else(dash, |r| r.worst.to_string()),
+            format!("{}-{}", stat.record.wins, stat.record.losses),
+            stat.record
+                .win_rate()
+                .map_or_else(dash, |rate| format!("{:.0}%", rate * 100.0)),
+            stat.controversy.map_or_else(dash, |c| format!("{:.2}", c)),
+        );
+    }
+
+    println!();
+    println!("Ranks come from each user's latest completed ranking of all items.");
+    println!("Controversy runs from 0 (everyone agrees) to 1 (split between top and bottom).");
+    println!("Win rates count every answered comparison, including unfinished sessions.");
+    println!();
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -241,6 +476,7 @@ mod tests {
             rankset: Some(list),
             active_user_id: None,
             active_strategy: "merge".to_string(),
+            active_strategy_config: None,
         }
     }
 
@@ -342,6 +578,87 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("already exists"));
     }
 
+    #[test]
+    fn test_set_group() {
+        let mut state = create_test_state();
+        state
+            .rankset
+            .as_mut()
+            .unwrap()
+            .add_item(Item::new("red".to_string()))
+            .unwrap();
+
+        let result = set_group(
+            Some(&mut state),
+            "red".to_string(),
+            Some("warm".to_string()),
+        );
+        assert!(result.is_ok());
+        let item = state.rankset.as_ref().unwrap().get_item("red").unwrap();
+        assert_eq!(item.group.as_deref(), Some("warm"));
+
+        let result = set_group(Some(&mut state), "red".to_string(), None);
+        assert!(result.is_ok());
+        let item = state.rankset.as_ref().unwrap().get_item("red").unwrap();
+        assert_eq!(item.group, None);
+    }
+
+    #[test]
+    fn test_set_details() {
+        let mut state = create_test_state();
+        state
+            .rankset
+            .as_mut()
+            .unwrap()
+            .add_item(Item::new("rust".to_string()))
+            .unwrap();
+
+        let result = set(
+            Some(&mut state),
+            "rust".to_string(),
+            Some("Memory safety without GC".to_string()),
+            Some("https://www.rust-lang.org".to_string()),
+            vec!["systems".to_string(), "compiled".to_string()],
+            vec![],
+            vec!["year=2015".to_string()],
+        );
+        assert!(result.is_ok());
+        let item = state.rankset.as_ref().unwrap().get_item("rust").unwrap();
+        assert_eq!(
+            item.description.as_deref(),
+            Some("Memory safety without GC")
+        );
+        assert_eq!(item.tags, vec!["systems", "compiled"]);
+        assert_eq!(item.attributes["year"], "2015");
+
+        let result = set(
+            Some(&mut state),
+            "rust".to_string(),
+            Some(String::new()),
+            None,
+            vec![],
+            vec!["systems".to_string()],
+            vec!["year=".to_string()],
+        );
+        assert!(result.is_ok());
+        let item = state.rankset.as_ref().unwrap().get_item("rust").unwrap();
+        assert_eq!(item.description, None);
+        assert!(item.url.is_some());
+        assert_eq!(item.tags, vec!["compiled"]);
+        assert!(item.attributes.is_empty());
+
+        let result = set(
+            Some(&mut state),
+            "rust".to_string(),
+            None,
+            None,
+            vec![],
+            vec![],
+            vec!["year".to_string()],
+        );
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_edit_no_state() {
         let result = edit(None, "id".to_string(), "value".to_string());
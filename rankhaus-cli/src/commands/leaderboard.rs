@@ -0,0 +1,175 @@
+use crate::state::AppState;
+use crate::LeaderboardArgs;
+use anyhow::Result;
+use rankhaus::pooled;
+use rankhaus::Id;
+use serde_json::json;
+
+/// Standard errors either side of a rating for a 95% interval
+const Z_95: f64 = 1.96;
+
+pub fn execute(args: LeaderboardArgs, state: Option<&mut AppState>) -> Result<()> {
+    let rankset = state.and_then(|s| s.rankset.as_ref()).ok_or_else(|| {
+        anyhow::anyhow!("No rankset loaded. Use 'init <name>' or 'load <file>' first.")
+    })?;
+
+    let criterion = match &args.criterion {
+        Some(name) => Some(rankset.get_criterion(name)?.name.as_str()),
+        None => None,
+    };
+
+    let board = pooled::leaderboard(rankset, criterion);
+    let value = |id: &Id| {
+        rankset
+            .get_item(id.as_str())
+            .map(|item| item.value.clone())
+            .unwrap_or_else(|_| id.to_string())
+    };
+
+    if args.json {
+        let output = json!({
+            "criterion": criterion,
+            "comparisons": board.comparisons,
+            "users": board.users,
+            "sessions": board.sessions,
+            "cleared_sessions": board.cleared_sessions,
+            "ratings": board.ratings.iter().map(|r| {
+                let (low, high) = r.interval(Z_95);
+                json!({
+                    "id": r.item,
+                    "value": value(&r.item),
+                    "rating": r.rating,
+                    "std_error": r.std_error,
+                    "interval_95": [low, high],
+                    "wins": r.record.wins,
+                    "losses": r.record.losses,
+                })
+            }).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!();
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!(
+        "  Leaderboard from {} comparisons by {} user{}{}",
+        board.comparisons,
+        board.users,
+        if board.users == 1 { "" } else { "s" },
+        criterion
+            .map(|c| format!(" (criterion: {})", c))
+            .unwrap_or_default()
+    );
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    if board.comparisons == 0 {
+        println!();
+        println!("  No comparisons to rate items from.");
+    } else {
+        println!();
+        println!(
+            "  {:>4}  {:<24} {:>7} {:>17} {:>9}",
+            "Rank", "Item", "Rating", "95% range", "Won-Lost"
+        );
+        for (i, rating) in board.ratings.iter().enumerate() {
+            let (low, high) = rating.interval(Z_95);
+            println!(
+                "  {:>4}  {:<24} {:>7.0} {:>17} {:>9}",
+                i + 1,
+                value(&rating.item),
+                rating.rating,
+                format!("{:.0} – {:.0}", low, high),
+                format!("{}-{}", rating.record.wins, rating.record.losses)
+            );
+        }
+        println!();
+        println!("  Bradley–Terry ratings on the Elo scale: 400 points apart means 10:1");
+        println!("  odds. Items with few answers are pulled towards 1500 and have wide");
+        println!("  ranges; overlapping ranges mean the order between them is unsure.");
+    }
+
+    if board.cleared_sessions > 0 {
+        println!();
+        println!(
+            "  {} finished session{} cleared {} comparisons and count for nothing.",
+            board.cleared_sessions,
+            if board.cleared_sessions == 1 { "" } else { "s" },
+            if board.cleared_sessions == 1 {
+                "its"
+            } else {
+                "their"
+            }
+        );
+        if !rankset.meta.keep_comparisons {
+            println!("  Keep them from now on with 'ranksets keep-comparisons on'.");
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankhaus::{Item, RankSet, Ranking, User};
+
+    fn create_test_state() -> AppState {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        for name in ["a", "b", "c"] {
+            let mut item = Item::new(name.to_string());
+            item.id = Id::from(name);
+            rankset.add_item(item).unwrap();
+        }
+        let user = User::new("alice".to_string(), None);
+        let mut ranking = Ranking::new(user.id.clone(), "merge".to_string());
+        for (winner, loser) in [("a", "b"), ("b", "c")] {
+            ranking
+                .session
+                .add_comparison(Id::from(winner), Id::from(loser), Id::from(winner));
+        }
+        rankset.add_user(user).unwrap();
+        rankset.rankings.push(ranking);
+
+        AppState {
+            rankset: Some(rankset),
+            active_user_id: None,
+            active_strategy: "merge".to_string(),
+            active_strategy_config: None,
+        }
+    }
+
+    fn args(json: bool) -> LeaderboardArgs {
+        LeaderboardArgs {
+            criterion: None,
+            json,
+        }
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let mut state = create_test_state();
+        assert!(execute(args(false), Some(&mut state)).is_ok());
+        assert!(execute(args(true), Some(&mut state)).is_ok());
+
+        let mut unknown = args(false);
+        unknown.criterion = Some("taste".to_string());
+        assert!(execute(unknown, Some(&mut state)).is_err());
+    }
+
+    #[test]
+    fn test_leaderboard_without_comparisons() {
+        let mut state = create_test_state();
+        let rankset = state.rankset.as_mut().unwrap();
+        rankset.rankings[0].session.clear_comparisons();
+        rankset.rankings[0].session.info.complete();
+        assert_eq!(pooled::leaderboard(rankset, None).cleared_sessions, 1);
+        assert!(execute(args(false), Some(&mut state)).is_ok());
+    }
+
+    #[test]
+    fn test_leaderboard_no_state() {
+        assert!(execute(args(false), None).is_err());
+    }
+}
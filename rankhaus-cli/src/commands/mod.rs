@@ -2,12 +2,18 @@ use crate::state::AppState;
 use crate::Commands;
 use anyhow::Result;
 
+mod aggregate;
+mod agreement;
+mod compare;
+mod criteria;
 mod init;
 mod items;
+mod leaderboard;
 mod load;
 mod rank;
 mod ranksets;
 mod sessions;
+mod simulate;
 mod strategy;
 mod users;
 
@@ -18,8 +24,14 @@ pub fn execute(command: Commands) -> Result<()> {
         Commands::Items { command } => items::execute(command, None),
         Commands::Users { command } => users::execute(command, None),
         Commands::Strategies { command } => strategy::execute(command, None),
-        Commands::Rank => rank::start(None),
+        Commands::Criteria { command } => criteria::execute(command, None),
+        Commands::Rank(args) => rank::start(args, None),
         Commands::Sessions { command } => sessions::execute(command, None),
+        Commands::Aggregate(args) => aggregate::execute(args, None),
+        Commands::Compare(args) => compare::execute(args, None),
+        Commands::Agreement(args) => agreement::execute(args, None),
+        Commands::Leaderboard(args) => leaderboard::execute(args, None),
+        Commands::Simulate(args) => simulate::execute(args),
     }
 }
 
@@ -30,7 +42,13 @@ pub fn execute_with_state(command: Commands, state: &mut AppState) -> Result<()>
         Commands::Items { command } => items::execute(command, Some(state)),
         Commands::Users { command } => users::execute(command, Some(state)),
         Commands::Strategies { command } => strategy::execute(command, Some(state)),
-        Commands::Rank => rank::start(Some(state)),
+        Commands::Criteria { command } => criteria::execute(command, Some(state)),
+        Commands::Rank(args) => rank::start(args, Some(state)),
         Commands::Sessions { command } => sessions::execute(command, Some(state)),
+        Commands::Aggregate(args) => aggregate::execute(args, Some(state)),
+        Commands::Compare(args) => compare::execute(args, Some(state)),
+        Commands::Agreement(args) => agreement::execute(args, Some(state)),
+        Commands::Leaderboard(args) => leaderboard::execute(args, Some(state)),
+        Commands::Simulate(args) => simulate::execute(args),
     }
 }
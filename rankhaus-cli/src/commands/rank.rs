@@ -1,13 +1,37 @@
 use crate::state::AppState;
+use crate::{GroupBy, RankArgs, WarmStartFrom};
 use anyhow::{bail, Context, Result};
+use rankhaus::budget::Budget;
+use rankhaus::constraint::{self, Constraint};
+use rankhaus::filter::ItemFilter;
+use rankhaus::group::{self, GroupMode};
+use rankhaus::prompt::Prompt;
+use rankhaus::ranking::{WarmStart, WarmStartSource};
+use rankhaus::score::{self, ScoreSheet};
+use rankhaus::seed::{self, Stream};
 use rankhaus::session::SessionStatus;
-use rankhaus::strategy::merge::MergeStrategy;
-#[cfg(feature = "quicksort")]
-use rankhaus::strategy::quicksort::QuickSortStrategy;
-use rankhaus::strategy::RankStrategy;
-use rankhaus::Ranking;
+use rankhaus::strategy::{self, RankStrategy, StrategyConfig};
+use rankhaus::tier::{self, TierList};
+use rankhaus::timing::LatencyStats;
+use rankhaus::{consistency, presentation, Item, RankResult, Ranking};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
 
-pub fn start(state: Option<&mut AppState>) -> Result<()> {
+/// Ask a consistency probe after every this many new comparisons
+const PROBE_INTERVAL: usize = 8;
+
+/// How a run of comparisons ended
+#[derive(PartialEq)]
+enum Outcome {
+    /// The strategy has no more pairs to ask
+    Complete,
+    /// The ranking's budget was used up first
+    OutOfBudget,
+    /// The user quit part way through
+    Quit,
+}
+
+pub fn start(args: RankArgs, state: Option<&mut AppState>) -> Result<()> {
     let app_state = state.ok_or_else(|| anyhow::anyhow!("No state available"))?;
 
     // Check prerequisites
@@ -34,7 +58,34 @@ pub fn start(state: Option<&mut AppState>) -> Result<()> {
         bail!("No items to rank. Use 'items add' to add items first.");
     }
 
-    if rankset.items.len() == 1 {
+    let mut ranking = match args.seed {
+        Some(seed) => {
+            // Earlier replays of the seed already hold its first session IDs
+            let replay = rankset
+                .rankings
+                .iter()
+                .filter(|r| r.seed == Some(seed))
+                .count();
+            Ranking::with_seed(
+                active_user_id.clone(),
+                app_state.active_strategy.clone(),
+                seed,
+                replay as u64,
+            )
+        }
+        None => Ranking::new(active_user_id.clone(), app_state.active_strategy.clone()),
+    };
+    if let Some(config) = &app_state.active_strategy_config {
+        ranking.strategy_config = Some(config.clone());
+    }
+    ranking.filter = parse_filter(rankset, &args)?;
+    let item_count = ranked_item_ids(rankset, &ranking).len();
+
+    if item_count == 0 {
+        bail!("No items match the filter.");
+    }
+
+    if item_count == 1 {
         bail!("Need at least 2 items to rank.");
     }
 
@@ -46,33 +97,174 @@ pub fn start(state: Option<&mut AppState>) -> Result<()> {
         user.username.clone()
     };
     println!("\n🎯 Starting ranking session for user: {}", user_label);
-    println!("Items to rank: {}", rankset.items.len());
+    match &ranking.filter {
+        Some(filter) => println!(
+            "Items to rank: {} of {} ({})",
+            item_count,
+            rankset.items.len(),
+            filter.describe()
+        ),
+        None => println!("Items to rank: {}", item_count),
+    }
+    if let Some(seed) = args.seed {
+        println!("🎲 Seed: {}", seed);
+    }
     println!();
 
-    // Create strategy based on active strategy
-    let item_ids: Vec<_> = rankset.items.keys().map(|k| k.clone().into()).collect();
+    // Check strategy is available
+    if !strategy::available().contains(&app_state.active_strategy.as_str()) {
+        bail!(
+            "Unknown strategy: {}. Use 'strategies list' to see available strategies.",
+            app_state.active_strategy
+        );
+    }
+
+    if let Some(name) = &args.criterion {
+        let criterion = rankset.get_criterion(name).map_err(|_| {
+            anyhow::anyhow!(
+                "Unknown criterion: '{}'. Use 'criteria list' to see criteria.",
+                name
+            )
+        })?;
+        println!("🧭 Criterion: {} — {}", criterion.name, criterion.prompt());
+        println!();
+        ranking.criterion = Some(criterion.name.clone());
+    }
+    let criterion = ranking.criterion.as_deref();
+    let filter = ranking.filter.as_ref();
 
-    match app_state.active_strategy.as_str() {
-        "merge" => {
-            let mut strategy = MergeStrategy::new(item_ids);
-            let mut ranking =
-                Ranking::new(active_user_id.clone(), app_state.active_strategy.clone());
-            let session_id = ranking.session.info.id.clone();
-            perform_ranking(rankset, &mut strategy, &mut ranking, session_id)
+    if let Some(from) = args.warm_start {
+        let warm_start = match from {
+            WarmStartFrom::Previous => {
+                let previous = rankset
+                    .latest_ranking(&active_user_id, criterion, filter)
+                    .ok_or_else(|| anyhow::anyhow!("No completed ranking to warm start from"))?;
+                println!(
+                    "🌱 Warm start from your ranking in session {}",
+                    previous.session.info.id.as_str()
+                );
+                WarmStart {
+                    source: WarmStartSource::Previous {
+                        session_id: previous.session.info.id.clone(),
+                    },
+                    order: previous
+                        .result
+                        .as_ref()
+                        .and_then(|r| r.order.clone())
+                        .unwrap_or_default(),
+                }
+            }
+            WarmStartFrom::Consensus => {
+                let order = rankset.consensus_order(criterion, filter).ok_or_else(|| {
+                    anyhow::anyhow!("No completed rankings to build a consensus from")
+                })?;
+                println!("🌱 Warm start from the group consensus");
+                WarmStart {
+                    source: WarmStartSource::Consensus,
+                    order,
+                }
+            }
+        };
+        ranking.warm_start = Some(warm_start);
+        println!();
+    }
+
+    ranking.constraints = parse_constraints(rankset, &ranking, &args)?;
+    if !ranking.constraints.is_empty() {
+        println!("📌 Constraints:");
+        for c in &ranking.constraints {
+            println!("  {}", describe_constraint(rankset, c));
         }
-        #[cfg(feature = "quicksort")]
-        "quicksort" => {
-            let mut strategy = QuickSortStrategy::new(item_ids);
-            let mut ranking =
-                Ranking::new(active_user_id.clone(), app_state.active_strategy.clone());
-            let session_id = ranking.session.info.id.clone();
-            perform_ranking(rankset, &mut strategy, &mut ranking, session_id)
+        println!();
+    }
+
+    if args.tiers {
+        let labels: Vec<String> = if args.tier_labels.is_empty() {
+            tier::DEFAULT_LABELS.iter().map(|l| l.to_string()).collect()
+        } else {
+            args.tier_labels
+                .iter()
+                .map(|l| l.trim().to_string())
+                .collect()
+        };
+        println!("🗂️  Tier list: {}", labels.join(" / "));
+        if args.order_tiers {
+            println!("Items will be ordered within each tier afterwards");
         }
-        _ => bail!(
-            "Unknown strategy: {}. Use 'strategies list' to see available strategies.",
-            app_state.active_strategy
-        ),
+        println!();
+        ranking.tier_list = Some(TierList::new(labels, args.order_tiers)?);
     }
+
+    if args.scores {
+        println!(
+            "🔢 Score mode: rate each item from {} to {}; scores within {} are compared",
+            score::MIN_SCORE,
+            score::MAX_SCORE,
+            args.score_tolerance
+        );
+        println!();
+        ranking.score_sheet = Some(ScoreSheet::new(args.score_tolerance));
+    }
+
+    if let Some(by) = args.by_group {
+        let item_ids: BTreeSet<rankhaus::Id> =
+            ranked_item_ids(rankset, &ranking).into_iter().collect();
+        let groups = group::partition(
+            rankset
+                .items
+                .values()
+                .filter(|item| item_ids.contains(&item.id)),
+        );
+        if groups.len() < 2 {
+            bail!("Items need at least 2 groups. Use 'items group <item> <group>' first.");
+        }
+        let (mode, how) = match by {
+            GroupBy::Merge => (GroupMode::Merge, "then merged"),
+            GroupBy::Representatives => (
+                GroupMode::Representatives,
+                "then ordered by their best items",
+            ),
+        };
+        println!("📂 Ranking within {} groups, {}", groups.len(), how);
+        println!();
+        ranking.group_mode = Some(mode);
+    }
+
+    if let Some(seconds) = args.weak_after {
+        if !seconds.is_finite() || seconds <= 0.0 {
+            bail!("--weak-after must be a positive number of seconds");
+        }
+        if !strategy::uses_weights(&ranking.strategy) {
+            bail!(
+                "--weak-after has no effect with the {} strategy, which treats every answer alike. Select a rating strategy such as elo first.",
+                ranking.strategy
+            );
+        }
+        println!(
+            "🐢 Answers slower than {}s count as weak preferences",
+            seconds
+        );
+        println!();
+        ranking.weak_after_ms = Some((seconds * 1000.0).round() as u64);
+    }
+
+    if args.max_comparisons.is_some() || args.time_limit.is_some() {
+        if args.max_comparisons == Some(0) || args.time_limit == Some(0) {
+            bail!("--max-comparisons and --time-limit must be at least 1");
+        }
+        let budget = Budget {
+            max_comparisons: args.max_comparisons,
+            time_limit_secs: args.time_limit,
+        };
+        println!(
+            "⏱️  Budget: {}, then finish with the best estimate",
+            budget.describe()
+        );
+        println!();
+        ranking.budget = Some(budget);
+    }
+
+    run_ranking(rankset, ranking)
 }
 
 pub fn resume(session_id: String, state: Option<&mut AppState>) -> Result<()> {
@@ -85,17 +277,8 @@ pub fn resume(session_id: String, state: Option<&mut AppState>) -> Result<()> {
         .ok_or_else(|| anyhow::anyhow!("No rankset loaded. Use 'init' or 'load' first."))?;
 
     // Find the in-progress session
-    let ranking_idx = rankset
-        .rankings
-        .iter()
-        .position(|r| {
-            let id_str = r.session.info.id.as_str();
-            (id_str == session_id || id_str.starts_with(&session_id))
-                && r.session.info.status == SessionStatus::InProgress
-        })
-        .ok_or_else(|| anyhow::anyhow!("No in-progress session found with ID '{}'", session_id))?;
-
-    let mut ranking = rankset.rankings.remove(ranking_idx);
+    let ranking_idx = find_in_progress(rankset, &session_id)?;
+    let ranking = rankset.rankings.remove(ranking_idx);
     let session_id = ranking.session.info.id.clone();
 
     println!("\n🔄 Resuming ranking session: {}", session_id.as_str());
@@ -105,86 +288,430 @@ pub fn resume(session_id: String, state: Option<&mut AppState>) -> Result<()> {
     );
     println!();
 
-    // Create strategy based on the ranking's strategy type
-    let item_ids: Vec<_> = rankset.items.keys().map(|k| k.clone().into()).collect();
+    continue_ranking(rankset, ranking)
+}
 
-    match ranking.strategy.as_str() {
-        "merge" => {
-            let mut strategy = MergeStrategy::new(item_ids);
+/// Switch an in-progress session to another strategy and continue ranking
+pub fn switch(
+    session_id: String,
+    strategy_name: String,
+    options: Vec<String>,
+    state: Option<&mut AppState>,
+) -> Result<()> {
+    let app_state = state.ok_or_else(|| anyhow::anyhow!("No state available"))?;
 
-            // Replay all saved comparisons to rebuild strategy state
-            println!("Restoring session state...");
-            for comparison in &ranking.session.comparisons {
-                let item_a = rankset.get_item(&comparison.a.to_string())?;
-                let item_b = rankset.get_item(&comparison.b.to_string())?;
-                strategy.compare(item_a, item_b, &comparison.winner)?;
-            }
-            println!(
-                "✓ Restored {} comparisons\n",
-                ranking.session.comparisons.len()
-            );
+    // Check prerequisites
+    let rankset = app_state
+        .rankset
+        .as_mut()
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded. Use 'init' or 'load' first."))?;
+
+    let available = strategy::available();
+    if !available.contains(&strategy_name.as_str()) {
+        bail!(
+            "Unknown strategy: '{}'. Available strategies: {}",
+            strategy_name,
+            available.join(", ")
+        );
+    }
+
+    // Find the in-progress session
+    let ranking_idx = find_in_progress(rankset, &session_id)?;
+    if rankset.rankings[ranking_idx].strategy == strategy_name {
+        bail!("Session already uses strategy '{}'", strategy_name);
+    }
+
+    let config = super::strategy::parse_config(&strategy_name, &options)?;
+    let mut ranking = rankset.rankings.remove(ranking_idx);
+    let session_id = ranking.session.info.id.clone();
+    let previous = ranking.strategy.clone();
+    ranking.switch_strategy(config);
+
+    println!(
+        "\n🔀 Switching session {} from {} to {}",
+        session_id.as_str(),
+        previous,
+        ranking.strategy
+    );
+    println!(
+        "Comparisons carried over: {}",
+        ranking.session.comparisons.len()
+    );
+    println!();
+
+    continue_ranking(rankset, ranking)
+}
+
+fn find_in_progress(rankset: &rankhaus::RankSet, session_id: &str) -> Result<usize> {
+    rankset
+        .rankings
+        .iter()
+        .position(|r| {
+            let id_str = r.session.info.id.as_str();
+            (id_str == session_id || id_str.starts_with(session_id))
+                && r.session.info.status == SessionStatus::InProgress
+        })
+        .ok_or_else(|| anyhow::anyhow!("No in-progress session found with ID '{}'", session_id))
+}
+
+/// Resolve the constraint flags against the rankset's items
+fn parse_constraints(
+    rankset: &rankhaus::RankSet,
+    ranking: &Ranking,
+    args: &RankArgs,
+) -> Result<Vec<Constraint>> {
+    let item_id = |identifier: &str| -> Result<rankhaus::Id> {
+        Ok(rankset.get_item(identifier.trim())?.id.clone())
+    };
+
+    let mut constraints = Vec::new();
+
+    for pair in &args.above {
+        let (higher, lower) = pair
+            .split_once('>')
+            .ok_or_else(|| anyhow::anyhow!("Expected HIGHER>LOWER, got '{}'", pair))?;
+        constraints.push(Constraint::Above {
+            higher: item_id(higher)?,
+            lower: item_id(lower)?,
+        });
+    }
 
-            perform_ranking(rankset, &mut strategy, &mut ranking, session_id)
+    if let Some(item) = &args.first {
+        constraints.push(Constraint::First {
+            item: item_id(item)?,
+        });
+    }
+
+    if let Some(item) = &args.last {
+        constraints.push(Constraint::Last {
+            item: item_id(item)?,
+        });
+    }
+
+    for fix in &args.fix {
+        let (item, position) = fix
+            .rsplit_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected ITEM=POSITION, got '{}'", fix))?;
+        let position = position
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid position in '{}'", fix))?;
+        constraints.push(Constraint::Position {
+            item: item_id(item)?,
+            position,
+        });
+    }
+
+    let item_ids = ranked_item_ids(rankset, ranking);
+    for c in &constraints {
+        if let Some(outside) = c.items().into_iter().find(|id| !item_ids.contains(id)) {
+            bail!(
+                "'{}' is not among the items being ranked",
+                rankset.get_item(&outside.to_string())?.value
+            );
         }
-        #[cfg(feature = "quicksort")]
-        "quicksort" => {
-            let mut strategy = QuickSortStrategy::new(item_ids);
-
-            // Replay all saved comparisons to rebuild strategy state
-            println!("Restoring session state...");
-            for comparison in &ranking.session.comparisons {
-                let item_a = rankset.get_item(&comparison.a.to_string())?;
-                let item_b = rankset.get_item(&comparison.b.to_string())?;
-                strategy.compare(item_a, item_b, &comparison.winner)?;
-            }
+    }
+
+    constraint::validate(&constraints, item_ids.len())?;
+    Ok(constraints)
+}
+
+/// Build the item filter from the subset flags, if any were given
+fn parse_filter(rankset: &rankhaus::RankSet, args: &RankArgs) -> Result<Option<ItemFilter>> {
+    let mut attributes = BTreeMap::new();
+    for attribute in &args.attributes {
+        let (key, value) = attribute
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Expected KEY=VALUE, got '{}'", attribute))?;
+        attributes.insert(key.trim().to_string(), value.trim().to_string());
+    }
+
+    let mut items = Vec::new();
+    for identifier in &args.only {
+        items.push(rankset.get_item(identifier.trim())?.id.clone());
+    }
+
+    let filter = ItemFilter::new(args.tags.clone(), attributes, items);
+    Ok((!filter.is_empty()).then_some(filter))
+}
+
+/// Describe a constraint using item values
+pub fn describe_constraint(rankset: &rankhaus::RankSet, constraint: &Constraint) -> String {
+    let name = |id: &rankhaus::Id| {
+        rankset
+            .get_item(&id.to_string())
+            .map(|item| item.value.clone())
+            .unwrap_or_else(|_| id.to_string())
+    };
+
+    match constraint {
+        Constraint::Above { higher, lower } => format!("{} > {}", name(higher), name(lower)),
+        Constraint::First { item } => format!("{} is first", name(item)),
+        Constraint::Last { item } => format!("{} is last", name(item)),
+        Constraint::Position { item, position } => format!("{} is #{}", name(item), position),
+    }
+}
+
+/// Rebuild the ranking's strategy from its recorded comparisons and continue
+fn continue_ranking(rankset: &mut rankhaus::RankSet, ranking: Ranking) -> Result<()> {
+    let current = strategy::version(&ranking.strategy);
+    if let (Some(then), Some(now)) = (ranking.strategy_version, current) {
+        if then != now {
             println!(
-                "✓ Restored {} comparisons\n",
-                ranking.session.comparisons.len()
+                "⚠️  Session was started with {} v{}, now v{}; answers carry over but new questions may differ",
+                ranking.strategy, then, now
             );
-
-            perform_ranking(rankset, &mut strategy, &mut ranking, session_id)
         }
-        _ => bail!(
-            "Unknown strategy: {}. Cannot resume session.",
-            ranking.strategy
-        ),
     }
+
+    println!("Restoring session state...");
+    println!(
+        "✓ Restored {} comparisons\n",
+        ranking.session.comparisons.len()
+    );
+
+    run_ranking(rankset, ranking)
 }
 
-fn perform_ranking<S: RankStrategy>(
+/// Collect any missing tier assignments or scores, then compare pairs until
+/// the strategy is done
+fn run_ranking(rankset: &mut rankhaus::RankSet, mut ranking: Ranking) -> Result<()> {
+    let session_id = ranking.session.info.id.clone();
+
+    if !assign_tiers(rankset, &mut ranking)? || !assign_scores(rankset, &mut ranking)? {
+        return suspend(rankset, &ranking, &session_id);
+    }
+
+    // Tiers alone are the result unless they should be ordered too
+    if matches!(&ranking.tier_list, Some(tiers) if !tiers.order_within) {
+        return finish(rankset, &mut ranking, RankResult::default(), 0);
+    }
+
+    if let Some(mode) = ranking.group_mode {
+        return rank_by_group(rankset, ranking, mode);
+    }
+
+    let mut strategy = build_strategy(&ranking, free_item_ids(rankset, &ranking), None, &[])?;
+    perform_ranking(rankset, strategy.as_mut(), &mut ranking)
+}
+
+/// Rank each item group on its own, then combine the group orders
+///
+/// Which phase a session is in follows from its comparisons: a group whose
+/// strategy is already complete after replay is not asked again.
+fn rank_by_group(
     rankset: &mut rankhaus::RankSet,
-    strategy: &mut S,
-    ranking: &mut Ranking,
-    session_id: rankhaus::Id,
+    mut ranking: Ranking,
+    mode: GroupMode,
 ) -> Result<()> {
-    // Estimate total comparisons for merge sort (worst case: n * log2(n))
-    let n = rankset.items.len() as f64;
-    let estimated_total = (n * n.log2()).ceil() as usize;
+    let session_id = ranking.session.info.id.clone();
+    let free: BTreeSet<rankhaus::Id> = free_item_ids(rankset, &ranking).into_iter().collect();
+    let groups = group::partition(
+        rankset
+            .items
+            .values()
+            .filter(|item| free.contains(&item.id)),
+    );
 
-    // Track comparisons made in this session (not including resumed ones)
+    let estimated_total = estimate_total(rankset, &ranking);
     let initial_count = ranking.session.comparisons.len();
 
-    // Perform comparisons
-    while let Some((a_id, b_id)) = strategy.next_comparison() {
-        let item_a = rankset.get_item(&a_id.to_string())?;
-        let item_b = rankset.get_item(&b_id.to_string())?;
+    // Rank within each group
+    let mut ranked_groups = Vec::new();
+    for item_group in &groups {
+        let mut strategy = build_strategy(&ranking, item_group.items.clone(), None, &[])?;
+        if !strategy.is_complete() {
+            println!("📂 Group: {}\n", item_group.name);
+        }
+        if compare_until_complete(
+            rankset,
+            strategy.as_mut(),
+            &mut ranking,
+            initial_count,
+            estimated_total,
+        )? != Outcome::Complete
+        {
+            return suspend(rankset, &ranking, &session_id);
+        }
+        ranked_groups.push(group::ItemGroup {
+            name: item_group.name.clone(),
+            items: strategy.finalize()?.order.unwrap_or_default(),
+        });
+    }
 
-        let current_count = ranking.session.comparisons.len() + 1;
+    // Combine the group orders; merging starts from the groups laid end to
+    // end and treats each group's order as known, so only pairs across
+    // groups are asked
+    let (item_ids, phase): (Vec<rankhaus::Id>, _) = match mode {
+        GroupMode::Merge => (
+            ranked_groups
+                .iter()
+                .flat_map(|g| g.items.iter().cloned())
+                .collect(),
+            "Merging groups",
+        ),
+        GroupMode::Representatives => (
+            ranked_groups
+                .iter()
+                .filter_map(|g| g.items.first().cloned())
+                .collect(),
+            "Ranking group representatives",
+        ),
+    };
+    let known: Vec<(rankhaus::Id, rankhaus::Id)> = match mode {
+        GroupMode::Merge => ranked_groups
+            .iter()
+            .flat_map(group::ItemGroup::implied_pairs)
+            .collect(),
+        GroupMode::Representatives => Vec::new(),
+    };
+    let mut strategy = build_strategy(&ranking, item_ids.clone(), Some(&item_ids), &known)?;
+    if !strategy.is_complete() {
+        println!("🔗 {}\n", phase);
+    }
+    if compare_until_complete(
+        rankset,
+        strategy.as_mut(),
+        &mut ranking,
+        initial_count,
+        estimated_total,
+    )? != Outcome::Complete
+    {
+        return suspend(rankset, &ranking, &session_id);
+    }
+
+    let combined = strategy.finalize()?.order.unwrap_or_default();
+    let order: Vec<rankhaus::Id> = match mode {
+        GroupMode::Merge => combined,
+        GroupMode::Representatives => group::arrange(&ranked_groups, &combined)
+            .into_iter()
+            .flat_map(|g| g.items)
+            .collect(),
+    };
+
+    let result = RankResult {
+        groups: Some(group::arrange(&ranked_groups, &order)),
+        order: Some(order),
+        ..Default::default()
+    };
+    finish(rankset, &mut ranking, result, initial_count)
+}
+
+/// Ask for a tier for every item not yet assigned one
+///
+/// Returns `false` if the user quit part way through.
+fn assign_tiers(rankset: &mut rankhaus::RankSet, ranking: &mut Ranking) -> Result<bool> {
+    let Some(tier_list) = &ranking.tier_list else {
+        return Ok(true);
+    };
+
+    let item_ids = free_item_ids(rankset, ranking);
+    let total = item_ids.len();
+    let pending = tier_list.unassigned(&item_ids);
+    let session_id = ranking.session.info.id.clone();
+    let known = constraint::implied_pairs(&ranking.constraints);
+
+    for item_id in pending {
+        let Some(tier_list) = ranking.tier_list.as_mut() else {
+            break;
+        };
+        let item = rankset.get_item(&item_id.to_string())?;
 
-        // Display comparison
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("  Comparison {} of ~{}", current_count, estimated_total);
+        println!("  Item {} of {}", tier_list.assignments.len() + 1, total);
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
         println!();
-        println!("  1️⃣  {}", item_a.value);
+        println!("  {}", rankset.meta.prompt.render(item));
         println!();
-        println!("  2️⃣  {}", item_b.value);
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+        let tier = loop {
+            print!(
+                "Which tier? ({}, or 'q' to quit): ",
+                tier_list.labels.join(", ")
+            );
+            use std::io::{self, Write};
+            io::stdout().flush()?;
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+
+            match input.trim() {
+                "q" | "Q" | "quit" => return Ok(false),
+                choice => match tier_list.find_tier(choice) {
+                    Some(tier) => match tier_list.conflict(&item_id, tier, &known) {
+                        Some((winner, loser)) => {
+                            let name = |id: &rankhaus::Id| {
+                                rankset
+                                    .get_item(id.as_str())
+                                    .map(|item| item.value.clone())
+                                    .unwrap_or_else(|_| id.to_string())
+                            };
+                            let other = if *winner == item_id { loser } else { winner };
+                            println!(
+                                "{} must rank above {}, which is in tier {}. Pick another tier.",
+                                name(winner),
+                                name(loser),
+                                tier_list.labels[tier_list.assignments[other]]
+                            );
+                        }
+                        None => break tier,
+                    },
+                    None => println!(
+                        "Invalid choice. Please enter one of {}, or 'q' to quit.",
+                        tier_list.labels.join(", ")
+                    ),
+                },
+            }
+        };
+
+        tier_list.assign(item_id, tier)?;
+        ranking.session.info.touch();
+
+        // Save progress after each assignment
+        rankset.rankings.retain(|r| r.session.info.id != session_id);
+        rankset.rankings.push(ranking.clone());
+        rankset.save().context("Failed to save progress")?;
+
+        println!();
+    }
+
+    Ok(true)
+}
+
+/// Ask for a score for every item not yet scored
+///
+/// Returns `false` if the user quit part way through.
+fn assign_scores(rankset: &mut rankhaus::RankSet, ranking: &mut Ranking) -> Result<bool> {
+    let Some(score_sheet) = &ranking.score_sheet else {
+        return Ok(true);
+    };
+
+    let item_ids = free_item_ids(rankset, ranking);
+    let total = item_ids.len();
+    let pending = score_sheet.unscored(&item_ids);
+    let session_id = ranking.session.info.id.clone();
+
+    for item_id in pending {
+        let Some(score_sheet) = ranking.score_sheet.as_mut() else {
+            break;
+        };
+        let item = rankset.get_item(&item_id.to_string())?;
+
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!("  Item {} of {}", score_sheet.scores.len() + 1, total);
+        println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        println!();
+        println!("  {}", rankset.meta.prompt.render(item));
         println!();
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
-        // Get user choice
-        let choice = loop {
-            print!("Which is better? (1, 2, or 'q' to quit): ");
+        let score = loop {
+            print!(
+                "Score? ({}-{}, or 'q' to quit): ",
+                score::MIN_SCORE,
+                score::MAX_SCORE
+            );
             use std::io::{self, Write};
             io::stdout().flush()?;
 
@@ -192,39 +719,289 @@ fn perform_ranking<S: RankStrategy>(
             io::stdin().read_line(&mut input)?;
 
             match input.trim() {
-                "1" => break Some(1),
-                "2" => break Some(2),
-                "q" | "Q" | "quit" => break None,
-                _ => println!("Invalid choice. Please enter 1, 2, or 'q' to quit."),
+                "q" | "Q" | "quit" => return Ok(false),
+                choice => match choice.parse::<u8>() {
+                    Ok(score) if (score::MIN_SCORE..=score::MAX_SCORE).contains(&score) => {
+                        break score
+                    }
+                    _ => println!(
+                        "Invalid score. Please enter {} to {}, or 'q' to quit.",
+                        score::MIN_SCORE,
+                        score::MAX_SCORE
+                    ),
+                },
             }
         };
 
-        // Check if user wants to quit
-        let choice = match choice {
-            Some(c) => c,
-            None => {
-                // Save progress and exit
-                rankset.rankings.retain(|r| r.session.info.id != session_id);
-                rankset.rankings.push(ranking.clone());
-                rankset.save().context("Failed to save progress")?;
-
-                println!("\n⏸️  Session suspended");
-                println!(
-                    "✓ Progress saved ({} comparisons)",
-                    ranking.session.comparisons.len()
-                );
-                println!("Resume with: sessions resume {}", session_id.as_str());
-                return Ok(());
+        score_sheet.set(item_id, score)?;
+        ranking.session.info.touch();
+
+        // Save progress after each score
+        rankset.rankings.retain(|r| r.session.info.id != session_id);
+        rankset.rankings.push(ranking.clone());
+        rankset.save().context("Failed to save progress")?;
+
+        println!();
+    }
+
+    Ok(true)
+}
+
+/// Items the ranking covers: those its filter picks, or all items
+fn ranked_item_ids(rankset: &rankhaus::RankSet, ranking: &Ranking) -> Vec<rankhaus::Id> {
+    match &ranking.filter {
+        Some(filter) => filter.apply(rankset.items.values()),
+        None => {
+            let mut item_ids: Vec<rankhaus::Id> =
+                rankset.items.keys().map(|k| k.clone().into()).collect();
+            item_ids.sort();
+            item_ids
+        }
+    }
+}
+
+/// Items the strategy ranks, i.e. the ranked items without a fixed position
+fn free_item_ids(rankset: &rankhaus::RankSet, ranking: &Ranking) -> Vec<rankhaus::Id> {
+    constraint::free_items(&ranked_item_ids(rankset, ranking), &ranking.constraints)
+}
+
+/// Create the ranking's strategy over `item_ids`, apply its warm start and
+/// replay the comparisons recorded so far
+///
+/// `hint` replaces the ranking's own warm start order when given, and
+/// `known` adds (winner, loser) pairs that are never asked.
+fn build_strategy(
+    ranking: &Ranking,
+    item_ids: Vec<rankhaus::Id>,
+    hint: Option<&[rankhaus::Id]>,
+    known: &[(rankhaus::Id, rankhaus::Id)],
+) -> Result<Box<dyn RankStrategy>> {
+    // Create strategy based on the ranking's strategy type and options
+    let config = match &ranking.strategy_config {
+        Some(config) => config.clone(),
+        None => {
+            let mut config = StrategyConfig::default_for(&ranking.strategy).map_err(|_| {
+                anyhow::anyhow!(
+                    "Unknown strategy: {}. Cannot resume session.",
+                    ranking.strategy
+                )
+            })?;
+            // Quicksort took the middle pivot before rankings had seeds
+            if ranking.seed.is_none() && ranking.strategy == "quicksort" {
+                config.set("pivot", "middle")?;
+            }
+            config
+        }
+    };
+    let mut strategy = strategy::create_configured(&config, item_ids);
+
+    if let Some(seed) = ranking.seed {
+        strategy.seed(seed);
+    }
+    if let Some(order) = hint.or(ranking.warm_start.as_ref().map(|w| w.order.as_slice())) {
+        strategy.warm_start(order);
+    }
+
+    // Known preferences are answered up front and never asked
+    for (winner, loser) in constraint::implied_pairs(&ranking.constraints) {
+        strategy.record_known(&winner, &loser, &winner)?;
+    }
+    for (winner, loser) in known {
+        strategy.record_known(winner, loser, winner)?;
+    }
+
+    // Items in different tiers are already ordered by their tiers
+    if let Some(tier_list) = &ranking.tier_list {
+        for (winner, loser) in tier_list.implied_pairs() {
+            strategy.record_known(&winner, &loser, &winner)?;
+        }
+    }
+
+    // Likewise for items whose scores are far enough apart
+    if let Some(score_sheet) = &ranking.score_sheet {
+        for (winner, loser) in score_sheet.implied_pairs() {
+            strategy.record_known(&winner, &loser, &winner)?;
+        }
+    }
+
+    // Replay all saved comparisons to rebuild strategy state, except those
+    // whose order `known` already settles
+    let settled: BTreeSet<(&rankhaus::Id, &rankhaus::Id)> = known
+        .iter()
+        .flat_map(|(winner, loser)| [(winner, loser), (loser, winner)])
+        .collect();
+    for comparison in &ranking.session.comparisons {
+        if settled.contains(&(&comparison.a, &comparison.b)) {
+            continue;
+        }
+        strategy.record_weighted(
+            &comparison.a,
+            &comparison.b,
+            &comparison.winner,
+            ranking.answer_weight(comparison),
+        )?;
+    }
+
+    Ok(strategy)
+}
+
+fn perform_ranking<S: RankStrategy + ?Sized>(
+    rankset: &mut rankhaus::RankSet,
+    strategy: &mut S,
+    ranking: &mut Ranking,
+) -> Result<()> {
+    let mut estimated_total = estimate_total(rankset, ranking);
+    if let Some(max) = ranking.budget.and_then(|b| b.max_comparisons) {
+        estimated_total = estimated_total.min(max);
+    }
+
+    // Track comparisons made in this session (not including resumed ones)
+    let initial_count = ranking.session.comparisons.len();
+
+    // Finalize ranking, with the best estimate so far if the budget ran out
+    let result =
+        match compare_until_complete(rankset, strategy, ranking, initial_count, estimated_total)? {
+            Outcome::Complete => strategy.finalize()?,
+            Outcome::OutOfBudget => strategy.finalize_best_effort()?,
+            Outcome::Quit => {
+                let session_id = ranking.session.info.id.clone();
+                return suspend(rankset, ranking, &session_id);
             }
         };
+    if result.order.is_none() {
+        bail!("No ranking order produced");
+    }
 
-        let winner = if choice == 1 { item_a } else { item_b };
-        strategy.compare(item_a, item_b, &winner.id)?;
+    finish(rankset, ranking, result, initial_count)
+}
 
-        // Record comparison in session
-        ranking
-            .session
-            .add_comparison(item_a.id.clone(), item_b.id.clone(), winner.id.clone());
+/// Estimate total comparisons for merge sort (worst case: n * log2(n)),
+/// counting only pairs within the same tier, group or close scores
+fn estimate_total(rankset: &rankhaus::RankSet, ranking: &Ranking) -> usize {
+    let n_log_n = |n: usize| (n as f64 * (n as f64).log2()).ceil() as usize;
+
+    let free = free_item_ids(rankset, ranking);
+    let sizes: Vec<usize> = if let Some(tier_list) = &ranking.tier_list {
+        tier_list
+            .tiers(&[])
+            .iter()
+            .map(|tier| tier.items.len())
+            .collect()
+    } else if let Some(score_sheet) = &ranking.score_sheet {
+        score_sheet.close_groups().iter().map(Vec::len).collect()
+    } else if ranking.group_mode.is_some() {
+        let groups = group::partition(
+            rankset
+                .items
+                .values()
+                .filter(|item| free.contains(&item.id)),
+        );
+        let combine = match ranking.group_mode {
+            // Merging runs takes about n comparisons per level of merges
+            Some(GroupMode::Merge) => free.len() * (groups.len() as f64).log2().ceil() as usize,
+            _ => n_log_n(groups.len()),
+        };
+        return groups.iter().map(|g| n_log_n(g.items.len())).sum::<usize>() + combine;
+    } else {
+        vec![free.len()]
+    };
+
+    sizes.into_iter().map(n_log_n).sum::<usize>().max(1)
+}
+
+/// Ask the strategy's comparisons until it is complete or the ranking's
+/// budget is used up, saving after each
+fn compare_until_complete<S: RankStrategy + ?Sized>(
+    rankset: &mut rankhaus::RankSet,
+    strategy: &mut S,
+    ranking: &mut Ranking,
+    initial_count: usize,
+    estimated_total: usize,
+) -> Result<Outcome> {
+    let session_id = ranking.session.info.id.clone();
+    let question = ranking
+        .criterion
+        .as_deref()
+        .and_then(|name| rankset.get_criterion(name).ok())
+        .map(|criterion| criterion.prompt())
+        .unwrap_or_else(|| rankset.meta.prompt.question().to_string());
+    let prompt = rankset.meta.prompt.clone();
+    // Sessions from before seeds were recorded show pairs in a fresh
+    // random order each run, without seeding their strategy mid-session
+    let seed = ranking.seed.unwrap_or_else(rand::random);
+
+    // Perform comparisons
+    while let Some((a_id, b_id)) = strategy.next_comparison() {
+        if ranking.budget.is_some_and(|b| b.is_spent(&ranking.session)) {
+            return Ok(Outcome::OutOfBudget);
+        }
+
+        let current_count = ranking.session.comparisons.len() + 1;
+
+        // Every so often, re-ask an earlier or implied pair to check consistency
+        let new_count = ranking.session.comparisons.len() - initial_count;
+        if new_count > 0 && new_count.is_multiple_of(PROBE_INTERVAL) {
+            let mut rng = seed::rng(seed, Stream::Probes, current_count as u64);
+            let probe = consistency::select_probe(&ranking.session.comparisons, &mut rng);
+            if let Some(probe) = probe {
+                let (first, second) = display_order(rankset, ranking, seed, &probe.a, &probe.b)?;
+
+                match ask(
+                    first,
+                    second,
+                    &prompt,
+                    &question,
+                    current_count,
+                    estimated_total,
+                )? {
+                    Some(Answer { choice, .. }) => {
+                        let answer = if choice == 1 { &first.id } else { &second.id };
+                        ranking
+                            .session
+                            .add_probe(probe.a, probe.b, probe.expected, answer.clone(), probe.kind)
+                            .shown_first = Some(first.id.clone());
+                        println!();
+                    }
+                    None => return Ok(Outcome::Quit),
+                }
+            }
+        }
+
+        let item_a = rankset.get_item(&a_id.to_string())?;
+        let item_b = rankset.get_item(&b_id.to_string())?;
+        let (first, second) = display_order(rankset, ranking, seed, &a_id, &b_id)?;
+
+        // Check if user wants to quit
+        let Answer { choice, latency_ms } = match ask(
+            first,
+            second,
+            &prompt,
+            &question,
+            current_count,
+            estimated_total,
+        )? {
+            Some(answer) => answer,
+            None => return Ok(Outcome::Quit),
+        };
+
+        let winner = if choice == 1 { first } else { second };
+
+        // Record comparison in session, along with which item was option 1
+        // and how long the answer took
+        let comparison =
+            ranking
+                .session
+                .add_comparison(item_a.id.clone(), item_b.id.clone(), winner.id.clone());
+        comparison.shown_first = Some(first.id.clone());
+        comparison.latency_ms = Some(latency_ms);
+        let comparison = comparison.clone();
+
+        let weight = ranking.answer_weight(&comparison);
+        if weight < 1.0 {
+            strategy.record_weighted(&item_a.id, &item_b.id, &winner.id, weight)?;
+        } else {
+            strategy.compare(item_a, item_b, &winner.id)?;
+        }
 
         // Save progress after each comparison
         // Remove existing session if it exists, then add updated one
@@ -235,21 +1012,65 @@ fn perform_ranking<S: RankStrategy>(
         println!();
     }
 
-    // Finalize ranking
-    let result = strategy.finalize()?;
-    let order = result
-        .order
-        .as_ref()
-        .ok_or_else(|| anyhow::anyhow!("No ranking order produced"))?;
+    Ok(Outcome::Complete)
+}
+
+/// The items of a pair in the order to show them for the ranking's next
+/// question
+fn display_order<'a>(
+    rankset: &'a rankhaus::RankSet,
+    ranking: &Ranking,
+    seed: u64,
+    a: &rankhaus::Id,
+    b: &rankhaus::Id,
+) -> Result<(&'a Item, &'a Item)> {
+    let index = ranking.session.comparisons.len() + ranking.session.probes.len();
+    let first = presentation::shown_first(seed, index, a, b);
+    let second = if first == a { b } else { a };
+    Ok((
+        rankset.get_item(&first.to_string())?,
+        rankset.get_item(&second.to_string())?,
+    ))
+}
+
+/// Complete the ranking with the strategy's result, save it and show it
+fn finish(
+    rankset: &mut rankhaus::RankSet,
+    ranking: &mut Ranking,
+    mut result: RankResult,
+    initial_count: usize,
+) -> Result<()> {
+    let session_id = ranking.session.info.id.clone();
+
+    // Group into tiers, then put back any items with a fixed position
+    if let Some(tier_list) = &ranking.tier_list {
+        let order = result.order.take().unwrap_or_default();
+        result.order = Some(tier_list.order(&order));
+        result.tiers = Some(tier_list.tiers(&order));
+    }
+    if let Some(score_sheet) = &ranking.score_sheet {
+        result.scores = Some(
+            score_sheet
+                .scores
+                .iter()
+                .map(|(id, score)| (id.clone(), *score))
+                .collect(),
+        );
+    }
+    if let Some(order) = &result.order {
+        result.order = Some(constraint::apply_fixed(order, &ranking.constraints)?);
+    }
 
     // Update ranking with final result and mark as complete
     ranking.result = Some(result.clone());
     ranking.session.info.complete();
 
     let total_comparisons = ranking.session.comparisons.len();
-
-    // Clear comparisons now that ranking is complete (save space)
-    ranking.session.comparisons.clear();
+    // Clear comparisons now that ranking is complete (save space), unless
+    // the rankset keeps them for pooled ratings
+    if !rankset.meta.keep_comparisons {
+        ranking.session.clear_comparisons();
+    }
 
     // Save final ranking
     rankset.rankings.retain(|r| r.session.info.id != session_id);
@@ -257,9 +1078,17 @@ fn perform_ranking<S: RankStrategy>(
     rankset.save().context("Failed to save rankset")?;
 
     // Display results
-    println!("\n✅ Ranking complete!");
+    if result.confidence.is_some() {
+        println!("\n⏱️  Budget used up, finished with the best estimate so far");
+    } else {
+        println!("\n✅ Ranking complete!");
+    }
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("  Final Ranking ({} comparisons)", total_comparisons);
+    if result.tiers.is_some() && total_comparisons == 0 {
+        println!("  Tier List");
+    } else {
+        println!("  Final Ranking ({} comparisons)", total_comparisons);
+    }
     if initial_count > 0 {
         println!(
             "  ({} resumed + {} new)",
@@ -270,13 +1099,378 @@ fn perform_ranking<S: RankStrategy>(
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!();
 
-    for (rank, item_id) in order.iter().enumerate() {
-        let item = rankset.get_item(&item_id.to_string())?;
-        println!("  {}. {}", rank + 1, item.value);
-    }
+    print_result(rankset, &result);
+    print_confidence(&result);
 
     println!();
+    print_consistency(rankset, ranking);
     println!("✓ Ranking saved");
 
     Ok(())
 }
+
+/// Note how settled a ranking that finished early is
+pub fn print_confidence(result: &RankResult) {
+    if let Some(confidence) = result.confidence {
+        println!();
+        println!(
+            "  Confidence: {:.0}% (stopped early; a full ranking may differ)",
+            confidence * 100.0
+        );
+    }
+}
+
+/// Print a ranking result, grouped by tier when it has tiers and tagged with
+/// each item's group when ranked by group
+pub fn print_result(rankset: &rankhaus::RankSet, result: &RankResult) {
+    let name = |id: &rankhaus::Id| {
+        rankset
+            .get_item(&id.to_string())
+            .map(|item| item.value.clone())
+            .ok()
+    };
+
+    if let Some(tiers) = &result.tiers {
+        let width = tiers.iter().map(|t| t.label.len()).max().unwrap_or(0);
+        for tier in tiers {
+            let names: Vec<_> = tier.items.iter().filter_map(name).collect();
+            println!("  {:<width$} │ {}", tier.label, names.join(", "));
+        }
+        return;
+    }
+
+    let group_of = |id: &rankhaus::Id| {
+        result
+            .groups
+            .as_ref()?
+            .iter()
+            .find(|g| g.items.contains(id))
+            .map(|g| g.name.as_str())
+    };
+
+    if let Some(order) = &result.order {
+        for (rank, item_id) in order.iter().enumerate() {
+            let Some(mut value) = name(item_id) else {
+                continue;
+            };
+            if let Some(group) = group_of(item_id) {
+                value = format!("{} [{}]", value, group);
+            }
+            match result.scores.as_ref().and_then(|s| s.get(item_id)) {
+                Some(score) => {
+                    println!("  {}. {} ({}/{})", rank + 1, value, score, score::MAX_SCORE)
+                }
+                None => println!("  {}. {}", rank + 1, value),
+            }
+        }
+    }
+}
+
+/// Print the session's consistency score and any preference cycles found
+pub fn print_consistency(rankset: &rankhaus::RankSet, ranking: &Ranking) {
+    let cycles = ranking.session.cycles();
+    if let Some(score) = ranking.session.consistency_score() {
+        let consistent = ranking
+            .session
+            .probes
+            .iter()
+            .filter(|p| p.is_consistent())
+            .count();
+        println!(
+            "Consistency:  {:.0}% ({}/{} checks agreed)",
+            score * 100.0,
+            consistent,
+            ranking.session.probes.len()
+        );
+    }
+
+    if !cycles.is_empty() {
+        println!("⚠ Preference cycles: {}", cycles.len());
+        for cycle in &cycles {
+            let names: Vec<_> = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|id| {
+                    rankset
+                        .get_item(&id.to_string())
+                        .map(|item| item.value.clone())
+                        .unwrap_or_else(|_| id.to_string())
+                })
+                .collect();
+            println!("  {}", names.join(" > "));
+        }
+    }
+
+    if ranking.session.consistency_score().is_some() || !cycles.is_empty() {
+        println!();
+    }
+}
+
+/// Print answer times and the pairs that took longest
+pub fn print_latency(rankset: &rankhaus::RankSet, stats: &LatencyStats) {
+    let Some(mean) = stats.mean_ms() else {
+        return;
+    };
+
+    match stats.median_ms {
+        Some(median) => println!(
+            "Answer time:  median {}, mean {} ({} timed)",
+            format_ms(median),
+            format_ms(mean),
+            stats.count
+        ),
+        None => println!(
+            "Answer time:  mean {} ({} timed)",
+            format_ms(mean),
+            stats.count
+        ),
+    }
+
+    if !stats.slowest.is_empty() {
+        let name = |id: &rankhaus::Id| {
+            rankset
+                .get_item(&id.to_string())
+                .map(|item| item.value.clone())
+                .unwrap_or_else(|_| id.to_string())
+        };
+        println!("Hardest pairs:");
+        for pair in &stats.slowest {
+            println!(
+                "  {} vs {} ({})",
+                name(&pair.a),
+                name(&pair.b),
+                format_ms(pair.latency_ms)
+            );
+        }
+    }
+    println!();
+}
+
+fn format_ms(ms: u64) -> String {
+    format!("{:.1}s", ms as f64 / 1000.0)
+}
+
+/// A choice between the two options shown, and how long it took
+struct Answer {
+    choice: u8,
+    /// Time from showing the pair to the answer, not counting time spent
+    /// with the details open
+    latency_ms: u64,
+}
+
+/// Show a pair and read the user's choice; `None` means the user wants to quit
+fn ask(
+    item_a: &Item,
+    item_b: &Item,
+    prompt: &Prompt,
+    question: &str,
+    current: usize,
+    estimated_total: usize,
+) -> Result<Option<Answer>> {
+    let option = |choice: u8, item: &Item| match prompt.label(choice) {
+        Some(label) => format!("{}: {}", label, prompt.render(item)),
+        None => prompt.render(item),
+    };
+    let mut choices = match (prompt.label(1), prompt.label(2)) {
+        (Some(first), Some(second)) => format!("1/{}, 2/{}", first, second),
+        _ => "1, 2".to_string(),
+    };
+    let has_details = item_a.has_details() || item_b.has_details();
+    if has_details {
+        choices.push_str(", 'i' for details");
+    }
+
+    // Display comparison
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!("  Comparison {} of ~{}", current, estimated_total);
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    println!();
+    println!("  1️⃣  {}", option(1, item_a));
+    println!();
+    println!("  2️⃣  {}", option(2, item_b));
+    println!();
+    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    // Get user choice
+    let asked = Instant::now();
+    let mut paused = Duration::ZERO;
+    loop {
+        print!("{} ({}, or 'q' to quit): ", question, choices);
+        use std::io::{self, Write};
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        match input.trim() {
+            "q" | "Q" | "quit" => return Ok(None),
+            "i" | "I" if has_details => {
+                // The answer clock stops until the details are closed
+                let opened = Instant::now();
+                for (choice, item) in [(1, item_a), (2, item_b)] {
+                    println!();
+                    println!("  {}", option(choice, item));
+                    super::items::print_details(item, "     ");
+                }
+                println!();
+                print!("Press Enter to go back to the question: ");
+                io::stdout().flush()?;
+                io::stdin().read_line(&mut String::new())?;
+                paused += opened.elapsed();
+            }
+            answer => match prompt.parse_choice(answer) {
+                Some(choice) => {
+                    let latency = asked.elapsed().saturating_sub(paused);
+                    return Ok(Some(Answer {
+                        choice,
+                        latency_ms: latency.as_millis() as u64,
+                    }));
+                }
+                None => println!("Invalid choice. Please enter {}, or 'q' to quit.", choices),
+            },
+        }
+    }
+}
+
+/// Save progress and exit the ranking loop
+fn suspend(
+    rankset: &mut rankhaus::RankSet,
+    ranking: &Ranking,
+    session_id: &rankhaus::Id,
+) -> Result<()> {
+    rankset
+        .rankings
+        .retain(|r| &r.session.info.id != session_id);
+    rankset.rankings.push(ranking.clone());
+    rankset.save().context("Failed to save progress")?;
+
+    println!("\n⏸️  Session suspended");
+    println!(
+        "✓ Progress saved ({} comparisons)",
+        ranking.session.comparisons.len()
+    );
+    println!("Resume with: sessions resume {}", session_id.as_str());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankhaus::{Id, RankSet, User};
+    use std::path::PathBuf;
+
+    /// Finish a ranking of three items with one comparison answered
+    fn finish_ranking(keep_comparisons: bool, path: &str) -> Ranking {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        rankset.file_path = Some(PathBuf::from(path));
+        rankset.meta.keep_comparisons = keep_comparisons;
+        let ids: Vec<Id> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let mut item = Item::new(name.to_string());
+                item.id = Id::from(*name);
+                rankset.add_item(item).unwrap();
+                Id::from(*name)
+            })
+            .collect();
+        let user = User::new("alice".to_string(), None);
+        let mut ranking = Ranking::new(user.id.clone(), "merge".to_string());
+        rankset.add_user(user).unwrap();
+        ranking
+            .session
+            .add_comparison(ids[0].clone(), ids[1].clone(), ids[0].clone());
+
+        let result = RankResult {
+            order: Some(ids),
+            ..Default::default()
+        };
+        finish(&mut rankset, &mut ranking, result, 0).unwrap();
+        std::fs::remove_file(path).unwrap();
+        rankset.rankings.pop().unwrap()
+    }
+
+    #[test]
+    fn test_finish_keeps_comparisons_when_asked() {
+        let kept = finish_ranking(true, "test_finish_keep.rankset");
+        assert!(kept.is_complete());
+        assert_eq!(kept.session.comparisons.len(), 1);
+
+        let cleared = finish_ranking(false, "test_finish_clear.rankset");
+        assert!(cleared.session.comparisons.is_empty());
+        assert_eq!(cleared.session.item_records()[&Id::from("a")].wins, 1);
+    }
+
+    #[test]
+    fn test_tiers_reject_fixed_positions() {
+        use clap::Parser;
+
+        for flag in [["--first", "a"], ["--last", "a"], ["--fix", "a=2"]] {
+            let args = ["rankhaus", "rank", "--tiers", flag[0], flag[1]];
+            assert!(crate::Cli::try_parse_from(args).is_err());
+        }
+        assert!(
+            crate::Cli::try_parse_from(["rankhaus", "rank", "--tiers", "--above", "a>b"]).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_finish_puts_every_item_in_one_tier() {
+        let path = "test_finish_tiers.rankset";
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        rankset.file_path = Some(PathBuf::from(path));
+        let ids: Vec<Id> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| Id::from(*name))
+            .collect();
+        for id in &ids {
+            let mut item = Item::new(id.to_string());
+            item.id = id.clone();
+            rankset.add_item(item).unwrap();
+        }
+
+        let mut ranking = Ranking::new(Id::from("u1"), "merge".to_string());
+        let mut tier_list = TierList::new(vec!["S".to_string(), "A".to_string()], false).unwrap();
+        for (id, tier) in ids.iter().zip([1, 0, 1, 0]) {
+            tier_list.assign(id.clone(), tier).unwrap();
+        }
+        ranking.tier_list = Some(tier_list);
+        finish(&mut rankset, &mut ranking, RankResult::default(), 0).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        let result = ranking.result.unwrap();
+        let mut tiered: Vec<Id> = result
+            .tiers
+            .unwrap()
+            .into_iter()
+            .flat_map(|tier| tier.items)
+            .collect();
+        tiered.sort();
+        assert_eq!(tiered, ids);
+        assert_eq!(result.order.unwrap().len(), ids.len());
+    }
+
+    #[test]
+    fn test_resume_ranking_from_before_seeds() {
+        let ids: Vec<Id> = (0..6).map(|i| Id::from(format!("i{}", i))).collect();
+        let mut ranking = Ranking::new(Id::from("u1"), "quicksort".to_string());
+        ranking.seed = None;
+        ranking.strategy_config = None;
+
+        // Answer by ID, resuming the session before every question
+        let mut middle = StrategyConfig::default_for("quicksort").unwrap();
+        middle.set("pivot", "middle").unwrap();
+        let mut live = strategy::create_configured(&middle, ids.clone());
+        while let Some((a, b)) = live.next_comparison() {
+            let resumed = build_strategy(&ranking, ids.clone(), None, &[]).unwrap();
+            assert_eq!(resumed.next_comparison(), Some((a.clone(), b.clone())));
+
+            let winner = a.clone().min(b.clone());
+            live.record_prior(&a, &b, &winner).unwrap();
+            ranking.session.add_comparison(a, b, winner);
+        }
+
+        let mut resumed = build_strategy(&ranking, ids.clone(), None, &[]).unwrap();
+        assert!(resumed.is_complete());
+        assert_eq!(resumed.finalize().unwrap().order, Some(ids));
+    }
+}
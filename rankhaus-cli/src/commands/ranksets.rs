@@ -1,7 +1,8 @@
 use crate::commands::{init, load};
 use crate::state::AppState;
 use crate::RanksetsCommands;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use rankhaus::prompt::Prompt;
 use std::fs;
 use std::path::Path;
 
@@ -16,6 +17,13 @@ pub fn execute(command: RanksetsCommands, state: Option<&mut AppState>) -> Resul
             description,
             author,
         } => init::execute(name, user, display_name, description, author, state),
+        RanksetsCommands::Prompt {
+            question,
+            labels,
+            item_template,
+            reset,
+        } => prompt(state, question, labels, item_template, reset),
+        RanksetsCommands::KeepComparisons { enabled } => keep_comparisons(state, enabled),
     }
 }
 
@@ -105,3 +113,121 @@ fn list(state: Option<&mut AppState>) -> Result<()> {
 
     Ok(())
 }
+
+fn prompt(
+    state: Option<&mut AppState>,
+    question: Option<String>,
+    labels: Option<Vec<String>>,
+    item_template: Option<String>,
+    reset: bool,
+) -> Result<()> {
+    let rankset = state
+        .and_then(|s| s.rankset.as_mut())
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded. Use 'ranksets load <file>' first."))?;
+
+    let changed = reset || question.is_some() || labels.is_some() || item_template.is_some();
+    if reset {
+        rankset.meta.prompt = Prompt::default();
+    }
+    // Empty values clear a setting
+    let non_empty = |value: String| {
+        let value = value.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    };
+    if let Some(question) = question {
+        rankset.meta.prompt.question = non_empty(question);
+    }
+    if let Some(labels) = labels {
+        let labels: Vec<String> = labels.into_iter().filter_map(non_empty).collect();
+        if labels
+            .iter()
+            .any(|l| ["1", "2", "i", "q", "quit"].contains(&l.to_lowercase().as_str()))
+        {
+            anyhow::bail!("Option labels cannot be 1, 2, i or q");
+        }
+        rankset.meta.prompt.labels = match <[String; 2]>::try_from(labels) {
+            Ok(labels) if labels[0].eq_ignore_ascii_case(&labels[1]) => {
+                anyhow::bail!("Option labels must differ")
+            }
+            Ok(labels) => Some(labels),
+            Err(labels) if labels.is_empty() => None,
+            Err(_) => anyhow::bail!("Expected two labels, e.g. --labels Left,Right"),
+        };
+    }
+    if let Some(template) = item_template {
+        rankset.meta.prompt.item_template = non_empty(template);
+    }
+
+    if changed {
+        rankset.save().context("Failed to save rankset")?;
+        println!("✓ Updated comparison prompt");
+    }
+
+    let prompt = &rankset.meta.prompt;
+    println!();
+    println!("  Question: {}", prompt.question());
+    match &prompt.labels {
+        Some([first, second]) => println!("  Options:  1 = {}, 2 = {}", first, second),
+        None => println!("  Options:  1, 2"),
+    }
+    println!(
+        "  Items:    {}",
+        prompt.item_template.as_deref().unwrap_or("{value}")
+    );
+    println!();
+
+    Ok(())
+}
+
+fn keep_comparisons(state: Option<&mut AppState>, enabled: Option<bool>) -> Result<()> {
+    let rankset = state
+        .and_then(|s| s.rankset.as_mut())
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded. Use 'ranksets load <file>' first."))?;
+
+    if let Some(enabled) = enabled {
+        rankset.meta.keep_comparisons = enabled;
+        rankset.save().context("Failed to save rankset")?;
+    }
+
+    if rankset.meta.keep_comparisons {
+        println!("✓ Finished sessions keep their comparisons for 'leaderboard'");
+    } else {
+        println!("✓ Finished sessions clear their comparisons to save space");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rankhaus::RankSet;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_keep_comparisons() {
+        let mut state = AppState::new();
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        rankset.file_path = Some(PathBuf::from("test_keep_comparisons.rankset"));
+        state.rankset = Some(rankset);
+        let keeping = |state: &AppState| state.rankset.as_ref().unwrap().meta.keep_comparisons;
+
+        assert!(keep_comparisons(Some(&mut state), None).is_ok());
+        assert!(!keeping(&state));
+
+        assert!(keep_comparisons(Some(&mut state), Some(true)).is_ok());
+        assert!(keeping(&state));
+        let saved = RankSet::load("test_keep_comparisons.rankset").unwrap();
+        assert!(saved.meta.keep_comparisons);
+
+        assert!(keep_comparisons(Some(&mut state), Some(false)).is_ok());
+        assert!(!keeping(&state));
+
+        std::fs::remove_file("test_keep_comparisons.rankset").unwrap();
+    }
+
+    #[test]
+    fn test_keep_comparisons_no_state() {
+        assert!(keep_comparisons(None, Some(true)).is_err());
+    }
+}
@@ -2,6 +2,9 @@ use crate::commands::rank;
 use crate::state::AppState;
 use crate::SessionsCommands;
 use anyhow::{bail, Context, Result};
+use rankhaus::filter::ItemFilter;
+use rankhaus::group::GroupMode;
+use rankhaus::ranking::WarmStartSource;
 use rankhaus::session::SessionStatus;
 
 pub fn execute(command: SessionsCommands, state: Option<&mut AppState>) -> Result<()> {
@@ -16,6 +19,11 @@ pub fn execute(command: SessionsCommands, state: Option<&mut AppState>) -> Resul
         SessionsCommands::Show { session_id } => show(state, session_id),
         SessionsCommands::Delete { session_id } => delete(state, session_id),
         SessionsCommands::Resume { session_id } => rank::resume(session_id, state),
+        SessionsCommands::Switch {
+            session_id,
+            strategy,
+            options,
+        } => rank::switch(session_id, strategy, options, state),
     }
 }
 
@@ -29,6 +37,15 @@ fn list(state: Option<&mut AppState>) -> Result<()> {
         return Ok(());
     }
 
+    // Rankings of different subsets are listed separately, all items first
+    let mut filters: Vec<Option<&ItemFilter>> = vec![None];
+    for ranking in &rankset.rankings {
+        if !filters.contains(&ranking.filter.as_ref()) {
+            filters.push(ranking.filter.as_ref());
+        }
+    }
+    let has_subsets = filters.len() > 1;
+
     println!("\n{:-<60}", "");
     println!(
         "{:<10} {:<12} {:<10} {:<10} {:<15}",
@@ -36,34 +53,51 @@ fn list(state: Option<&mut AppState>) -> Result<()> {
     );
     println!("{:-<60}", "");
 
-    for ranking in &rankset.rankings {
-        let user = rankset
-            .users
-            .get(&ranking.user_id.to_string())
-            .map(|u| u.username.as_str())
-            .unwrap_or("unknown");
-
-        let status = match ranking.session.info.status {
-            SessionStatus::InProgress => "in_progress",
-            SessionStatus::Completed => "completed",
-            SessionStatus::Abandoned => "abandoned",
-        };
+    for filter in filters {
+        let rankings: Vec<_> = rankset
+            .rankings
+            .iter()
+            .filter(|r| r.filter.as_ref() == filter)
+            .collect();
+        if rankings.is_empty() {
+            continue;
+        }
+        if has_subsets {
+            match filter {
+                Some(filter) => println!("Subset: {}", filter.describe()),
+                None => println!("All items"),
+            }
+        }
 
-        let created = ranking
-            .session
-            .info
-            .created
-            .format("%Y-%m-%d %H:%M")
-            .to_string();
+        for ranking in rankings {
+            let user = rankset
+                .users
+                .get(&ranking.user_id.to_string())
+                .map(|u| u.username.as_str())
+                .unwrap_or("unknown");
 
-        println!(
-            "{:<10} {:<12} {:<10} {:<10} {:<15}",
-            ranking.session.info.id.as_str(),
-            user,
-            ranking.strategy,
-            status,
-            created
-        );
+            let status = match ranking.session.info.status {
+                SessionStatus::InProgress => "in_progress",
+                SessionStatus::Completed => "completed",
+                SessionStatus::Abandoned => "abandoned",
+            };
+
+            let created = ranking
+                .session
+                .info
+                .created
+                .format("%Y-%m-%d %H:%M")
+                .to_string();
+
+            println!(
+                "{:<10} {:<12} {:<10} {:<10} {:<15}",
+                ranking.session.info.id.as_str(),
+                user,
+                ranking.strategy,
+                status,
+                created
+            );
+        }
     }
 
     println!();
@@ -98,7 +132,83 @@ fn show(state: Option<&mut AppState>, session_id: String) -> Result<()> {
     println!();
     println!("Session ID:   {}", ranking.session.info.id.as_str());
     println!("User:         {}", user);
-    println!("Strategy:     {}", ranking.strategy);
+    if let Some(criterion) = &ranking.criterion {
+        println!("Criterion:    {}", criterion);
+    }
+    if let Some(filter) = &ranking.filter {
+        println!("Subset:       {}", filter.describe());
+    }
+    let version = ranking
+        .strategy_version
+        .map(|v| format!(" v{}", v))
+        .unwrap_or_default();
+    match ranking.strategy_config.as_ref().map(|c| c.describe()) {
+        Some(options) if !options.is_empty() => {
+            println!(
+                "Strategy:     {}{} ({})",
+                ranking.strategy, version, options
+            )
+        }
+        _ => println!("Strategy:     {}{}", ranking.strategy, version),
+    }
+    if let Some(seed) = ranking.seed {
+        println!("Seed:         {}", seed);
+    }
+    for switch in &ranking.switches {
+        println!(
+            "  (switched from {} after {} comparisons)",
+            switch.from, switch.after_comparisons
+        );
+    }
+    if let Some(warm_start) = &ranking.warm_start {
+        match &warm_start.source {
+            WarmStartSource::Previous { session_id } => {
+                println!("Warm start:   previous ranking ({})", session_id.as_str())
+            }
+            WarmStartSource::Consensus => println!("Warm start:   group consensus"),
+        }
+    }
+    if !ranking.constraints.is_empty() {
+        println!("Constraints:");
+        for c in &ranking.constraints {
+            println!("  {}", rank::describe_constraint(rankset, c));
+        }
+    }
+    if let Some(tier_list) = &ranking.tier_list {
+        println!(
+            "Tiers:        {} ({} items assigned{})",
+            tier_list.labels.join(" / "),
+            tier_list.assignments.len(),
+            if tier_list.order_within {
+                ", ordered within tiers"
+            } else {
+                ""
+            }
+        );
+    }
+    if let Some(score_sheet) = &ranking.score_sheet {
+        println!(
+            "Scores:       {} items scored (tolerance {})",
+            score_sheet.scores.len(),
+            score_sheet.tolerance
+        );
+    }
+    if let Some(limit) = ranking.weak_after_ms {
+        println!(
+            "Weak after:   {:.1}s (slower answers count half)",
+            limit as f64 / 1000.0
+        );
+    }
+    if let Some(budget) = &ranking.budget {
+        println!("Budget:       {}", budget.describe());
+    }
+    if let Some(mode) = ranking.group_mode {
+        let how = match mode {
+            GroupMode::Merge => "ranked within groups, then merged",
+            GroupMode::Representatives => "ranked within groups, then by representatives",
+        };
+        println!("Groups:       {}", how);
+    }
     println!("Status:       {:?}", ranking.session.info.status);
     println!(
         "Created:      {}",
@@ -121,21 +231,30 @@ fn show(state: Option<&mut AppState>, session_id: String) -> Result<()> {
     }
 
     println!("Comparisons:  {}", ranking.session.comparisons.len());
+    let bias = ranking.session.position_bias();
+    if let Some(rate) = bias.first_rate() {
+        println!(
+            "Option 1:     picked {} of {} times ({:.0}%)",
+            bias.first,
+            bias.total(),
+            rate * 100.0
+        );
+    }
     println!();
 
+    rank::print_consistency(rankset, ranking);
+    rank::print_latency(rankset, &ranking.session.latency_stats());
+
     // Show ranking result if available
     if let Some(ref result) = ranking.result {
-        if let Some(ref order) = result.order {
+        if result.order.is_some() || result.tiers.is_some() {
             println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
             println!("  Final Ranking");
             println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
             println!();
 
-            for (rank, item_id) in order.iter().enumerate() {
-                if let Ok(item) = rankset.get_item(&item_id.to_string()) {
-                    println!("  {}. {}", rank + 1, item.value);
-                }
-            }
+            rank::print_result(rankset, result);
+            rank::print_confidence(result);
             println!();
         }
     }
@@ -0,0 +1,80 @@
+use crate::commands::strategy::parse_config;
+use crate::SimulateArgs;
+use anyhow::{bail, Result};
+use rankhaus::simulate::{Noise, Simulation};
+use rankhaus::strategy;
+
+pub fn execute(args: SimulateArgs) -> Result<()> {
+    let names: Vec<String> = if args.strategies.is_empty() {
+        strategy::available()
+            .into_iter()
+            .map(String::from)
+            .collect()
+    } else {
+        args.strategies.clone()
+    };
+    if !args.options.is_empty() && names.len() != 1 {
+        bail!("--opt needs exactly one --strategy");
+    }
+    if args.items < 2 {
+        bail!("Simulate at least 2 items");
+    }
+    if args.runs == 0 {
+        bail!("Simulate at least 1 run");
+    }
+
+    let noise: Noise = args.noise.parse()?;
+    let seed = args.seed.unwrap_or_else(rand::random);
+
+    println!(
+        "\n🧪 Simulating {} runs of {} items (noise: {}, seed: {})",
+        args.runs, args.items, noise, seed
+    );
+    println!();
+    println!(
+        "{:<20} {:>14} {:>9}  {:>15} {:>7}",
+        "Strategy", "Comparisons", "Range", "Kendall tau", "Worst"
+    );
+    println!("{:-<70}", "");
+
+    for name in &names {
+        let config = parse_config(name, &args.options)?;
+        // Options are only worth showing when they were changed
+        let label = if args.options.is_empty() {
+            name.clone()
+        } else {
+            format!("{} ({})", name, config.describe())
+        };
+        let report = Simulation {
+            strategy: config,
+            items: args.items,
+            runs: args.runs,
+            noise,
+            seed,
+        }
+        .run()?;
+
+        let comparisons = &report.comparisons;
+        let tau = &report.kendall_tau;
+        println!(
+            "{:<20} {:>7.1} ± {:<4.1} {:>9}  {:>6.3} ± {:<6.3} {:>7.3}",
+            label,
+            comparisons.mean,
+            comparisons.std_dev(),
+            format!("{}–{}", comparisons.min, comparisons.max),
+            tau.mean,
+            tau.std_dev(),
+            tau.min
+        );
+    }
+
+    let n = args.items as f64;
+    println!();
+    println!(
+        "Kendall tau is 1 for the true order and -1 for its reverse. For reference, n log2 n = {}.",
+        (n * n.log2()).ceil()
+    );
+    println!();
+
+    Ok(())
+}
@@ -1,97 +1,56 @@
 use crate::state::AppState;
 use crate::StrategyCommands;
 use anyhow::{bail, Result};
+use rankhaus::strategy::{self, StrategyConfig};
 
 pub fn execute(command: StrategyCommands, state: Option<&mut AppState>) -> Result<()> {
     match command {
         StrategyCommands::List => list(state),
-        StrategyCommands::Select { strategy } => select(strategy, state),
+        StrategyCommands::Select { strategy, options } => select(strategy, options, state),
     }
 }
 
 fn list(state: Option<&mut AppState>) -> Result<()> {
-    let active_strategy = state.map(|s| s.active_strategy.as_str());
+    let active_strategy = state.as_ref().map(|s| s.active_strategy.as_str());
+    let active_options = state
+        .as_ref()
+        .and_then(|s| s.active_strategy_config.as_ref())
+        .map(|c| c.describe())
+        .filter(|options| !options.is_empty());
 
     println!("Available strategies:");
 
-    let marker = if active_strategy == Some("merge") {
-        "*"
-    } else {
-        " "
-    };
-    println!("{} merge      - Merge sort (pairwise comparison)", marker);
-
-    #[cfg(feature = "quicksort")]
-    {
-        let marker = if active_strategy == Some("quicksort") {
-            "*"
-        } else {
-            " "
-        };
-        println!(
-            "{} quicksort  - Quick sort (pivot-based partitioning)",
-            marker
-        );
-    }
-
-    #[cfg(feature = "elo")]
-    {
-        let marker = if active_strategy == Some("elo") {
-            "*"
-        } else {
-            " "
-        };
-        println!("{} elo        - Elo rating system", marker);
-    }
-
-    #[cfg(feature = "tournament")]
-    {
-        let marker = if active_strategy == Some("tournament") {
+    for name in strategy::available() {
+        let marker = if active_strategy == Some(name) {
             "*"
         } else {
             " "
         };
-        println!("{} tournament - Tournament/knockout", marker);
+        println!("{} {:<10} - {}", marker, name, describe(name));
+        if active_strategy == Some(name) {
+            if let Some(options) = &active_options {
+                println!("  {:<10}   options: {}", "", options);
+            }
+        }
     }
 
-    #[cfg(feature = "condorcet")]
-    {
-        let marker = if active_strategy == Some("condorcet") {
-            "*"
-        } else {
-            " "
-        };
-        println!("{} condorcet  - Condorcet method", marker);
-    }
-
-    #[cfg(feature = "active")]
-    {
-        let marker = if active_strategy == Some("active") {
-            "*"
-        } else {
-            " "
-        };
-        println!("{} active     - Active learning", marker);
-    }
+    Ok(())
+}
 
-    #[cfg(feature = "btm")]
-    {
-        let marker = if active_strategy == Some("btm") {
-            "*"
-        } else {
-            " "
-        };
-        println!("{} btm        - Bradley-Terry model", marker);
+fn describe(name: &str) -> &'static str {
+    match name {
+        "merge" => "Merge sort (pairwise comparison)",
+        "quicksort" => "Quick sort (pivot-based partitioning)",
+        "elo" => "Elo rating system",
+        _ => "",
     }
-
-    Ok(())
 }
 
-fn select(strategy: String, state: Option<&mut AppState>) -> Result<()> {
+fn select(strategy: String, options: Vec<String>, state: Option<&mut AppState>) -> Result<()> {
     let app_state = state.ok_or_else(|| anyhow::anyhow!("No state available"))?;
 
     // Validate strategy is available
-    let valid_strategies = get_available_strategies();
+    let valid_strategies = strategy::available();
 
     if !valid_strategies.contains(&strategy.as_str()) {
         bail!(
@@ -101,33 +60,25 @@ fn select(strategy: String, state: Option<&mut AppState>) -> Result<()> {
         );
     }
 
-    app_state.active_strategy = strategy.clone();
-    println!("✓ Selected strategy: {}", strategy);
+    let config = parse_config(&strategy, &options)?;
+    match config.describe() {
+        options if options.is_empty() => println!("✓ Selected strategy: {}", strategy),
+        options => println!("✓ Selected strategy: {} ({})", strategy, options),
+    }
+    app_state.active_strategy = strategy;
+    app_state.active_strategy_config = Some(config);
 
     Ok(())
 }
 
-fn get_available_strategies() -> Vec<&'static str> {
-    #[allow(unused_mut)]
-    let mut strategies = vec!["merge"];
-
-    #[cfg(feature = "quicksort")]
-    strategies.push("quicksort");
-
-    #[cfg(feature = "elo")]
-    strategies.push("elo");
-
-    #[cfg(feature = "tournament")]
-    strategies.push("tournament");
-
-    #[cfg(feature = "condorcet")]
-    strategies.push("condorcet");
-
-    #[cfg(feature = "active")]
-    strategies.push("active");
-
-    #[cfg(feature = "btm")]
-    strategies.push("btm");
-
-    strategies
+/// Build a strategy's configuration from "KEY=VALUE" options
+pub fn parse_config(strategy: &str, options: &[String]) -> Result<StrategyConfig> {
+    let mut config = StrategyConfig::default_for(strategy)?;
+    for option in options {
+        let Some((key, value)) = option.split_once('=') else {
+            bail!("Invalid option '{}'. Use KEY=VALUE", option);
+        };
+        config.set(key.trim(), value)?;
+    }
+    Ok(config)
 }
@@ -1,7 +1,11 @@
+use crate::commands::rank;
 use crate::state::AppState;
 use crate::UsersCommands;
 use anyhow::{bail, Context, Result};
-use rankhaus::User;
+use rankhaus::history::History;
+use rankhaus::presentation::PositionBias;
+use rankhaus::timing::LatencyStats;
+use rankhaus::{Id, User};
 
 pub fn execute(command: UsersCommands, state: Option<&mut AppState>) -> Result<()> {
     // Check if list is loaded
@@ -26,6 +30,11 @@ pub fn execute(command: UsersCommands, state: Option<&mut AppState>) -> Result<(
         } => edit(state, identifier, new_display_name),
         UsersCommands::Select { identifier } => select(state, identifier),
         UsersCommands::Default { identifier } => default(state, identifier),
+        UsersCommands::Stats { identifier } => stats(state, identifier),
+        UsersCommands::History {
+            identifier,
+            criterion,
+        } => history(state, identifier, criterion),
     }
 }
 
@@ -234,10 +243,250 @@ fn default(state: Option<&mut AppState>, identifier: Option<String>) -> Result<(
     Ok(())
 }
 
+/// Answers needed before a lopsided first-option rate is flagged
+const MIN_ANSWERS_FOR_BIAS: usize = 20;
+
+/// First-option rates this far from an even split are flagged
+const BIAS_MARGIN: f64 = 0.15;
+
+fn stats(state: Option<&mut AppState>, identifier: Option<String>) -> Result<()> {
+    let rankset = state
+        .and_then(|s| s.rankset.as_ref())
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded"))?;
+
+    let mut users: Vec<&User> = match &identifier {
+        Some(identifier) => vec![rankset.get_user(identifier)?],
+        None => rankset.users.values().collect(),
+    };
+    users.sort_by(|a, b| a.username.cmp(&b.username));
+
+    println!("\nAnswering habits");
+    println!("{:-<70}", "");
+    println!(
+        "{:<20} {:>8} {:>12} {:>8} {:>10}",
+        "User", "Answers", "Picked 1st", "Rate", "Mean time"
+    );
+    println!("{:-<70}", "");
+
+    let mut timings = Vec::new();
+    for user in users {
+        let bias = position_bias(rankset, &user.id);
+        let rate = match bias.first_rate() {
+            Some(rate) => format!("{:.0}%", rate * 100.0),
+            None => "-".to_string(),
+        };
+        let flag = match bias.first_rate() {
+            Some(rate) if bias.total() >= MIN_ANSWERS_FOR_BIAS && rate - 0.5 > BIAS_MARGIN => {
+                "  ⚠ favors option 1"
+            }
+            Some(rate) if bias.total() >= MIN_ANSWERS_FOR_BIAS && 0.5 - rate > BIAS_MARGIN => {
+                "  ⚠ favors option 2"
+            }
+            _ => "",
+        };
+        let latency = LatencyStats::combine(
+            rankset
+                .rankings
+                .iter()
+                .filter(|r| r.user_id == user.id)
+                .map(|r| r.session.latency_stats()),
+        );
+        let mean = match latency.mean_ms() {
+            Some(ms) => format!("{:.1}s", ms as f64 / 1000.0),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:<20} {:>8} {:>12} {:>8} {:>10}{}",
+            user.username,
+            bias.total(),
+            bias.first,
+            rate,
+            mean,
+            flag
+        );
+        timings.push(latency);
+    }
+
+    println!();
+    println!("Only answers that recorded which item was shown first are counted.");
+    println!();
+
+    // A single user's hardest pairs
+    if let [latency] = timings.as_slice() {
+        rank::print_latency(rankset, latency);
+    }
+    Ok(())
+}
+
+/// How often a user picked the item shown first, over all their sessions
+fn position_bias(rankset: &rankhaus::RankSet, user_id: &Id) -> PositionBias {
+    rankset
+        .rankings
+        .iter()
+        .filter(|r| &r.user_id == user_id)
+        .map(|r| r.session.position_bias())
+        .fold(PositionBias::default(), |total, b| PositionBias {
+            first: total.first + b.first,
+            second: total.second + b.second,
+        })
+}
+
+/// Most snapshots shown side by side; older ones still count towards drift
+const HISTORY_COLUMNS: usize = 8;
+
+/// Most risers and fallers listed
+const TOP_MOVERS: usize = 5;
+
+fn history(
+    state: Option<&mut AppState>,
+    identifier: Option<String>,
+    criterion: Option<String>,
+) -> Result<()> {
+    let app_state = state.ok_or_else(|| anyhow::anyhow!("No state available"))?;
+    let rankset = app_state
+        .rankset
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("No rankset loaded"))?;
+
+    let user = match (&identifier, &app_state.active_user_id) {
+        (Some(identifier), _) => rankset.get_user(identifier)?,
+        (None, Some(user_id)) => rankset.get_user(user_id.as_str())?,
+        (None, None) => rankset.users.values().find(|u| u.default).ok_or_else(|| {
+            anyhow::anyhow!("No active user. Give a user or use 'users select <user>'.")
+        })?,
+    };
+    let criterion = match &criterion {
+        Some(name) => Some(rankset.get_criterion(name)?.name.as_str()),
+        None => None,
+    };
+
+    let history = History::for_user(rankset, &user.id, criterion);
+    if history.snapshots.is_empty() {
+        bail!(
+            "{} has no completed ranking of all items{}",
+            user.username,
+            criterion
+                .map(|c| format!(" on '{}'", c))
+                .unwrap_or_default()
+        );
+    }
+    let value = |id: &rankhaus::Id| {
+        rankset
+            .get_item(id.as_str())
+            .map(|item| item.value.clone())
+            .unwrap_or_else(|_| id.to_string())
+    };
+
+    println!(
+        "\nRanking history for {}{} ({} ranking{})",
+        user.username,
+        criterion.map(|c| format!(" on {}", c)).unwrap_or_default(),
+        history.snapshots.len(),
+        if history.snapshots.len() == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+    println!("{:-<70}", "");
+    println!(
+        "{:<4}{:<18} {:<10} {:<10} {:>6} {:>16}",
+        "#", "Completed", "Session", "Strategy", "Items", "Tau vs previous"
+    );
+    println!("{:-<70}", "");
+    for (i, snapshot) in history.snapshots.iter().enumerate() {
+        println!(
+            "{:<4}{:<18} {:<10} {:<10} {:>6} {:>16}",
+            i + 1,
+            snapshot.completed.format("%Y-%m-%d %H:%M"),
+            snapshot.session_id.as_str(),
+            snapshot.strategy,
+            snapshot.order.len(),
+            snapshot
+                .kendall_tau_to_previous
+                .map_or_else(|| "-".to_string(), |tau| format!("{:.2}", tau))
+        );
+    }
+
+    let Some(stability) = history.stability() else {
+        println!();
+        println!("Only one completed ranking so far. Rank again to see how it drifts.");
+        println!();
+        return Ok(());
+    };
+    println!();
+    println!(
+        "Overall stability: {:.2} (mean tau between consecutive rankings, 1 = unchanged)",
+        stability
+    );
+
+    for (title, movers) in [
+        ("Biggest risers", history.risers()),
+        ("Biggest fallers", history.fallers()),
+    ] {
+        if movers.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{} (first ranking → latest):", title);
+        for drift in movers.iter().take(TOP_MOVERS) {
+            let ranked: Vec<usize> = drift.positions.iter().flatten().copied().collect();
+            println!(
+                "  {:<24} {:>3} → {:<3} {}{}",
+                value(&drift.item),
+                ranked[0],
+                ranked[ranked.len() - 1],
+                if drift.change > 0 { "▲" } else { "▼" },
+                drift.change.abs()
+            );
+        }
+    }
+
+    let shown = history.snapshots.len().min(HISTORY_COLUMNS);
+    let skipped = history.snapshots.len() - shown;
+    println!();
+    // Columns are numbered like the timeline above, oldest first
+    let header: String = (skipped..history.snapshots.len())
+        .map(|i| format!(" {:>6}", format!("#{}", i + 1)))
+        .collect();
+    println!("{:<24}{} {:>10}", "Item", header, "Stability");
+    println!("{:-<70}", "");
+    for drift in &history.drift {
+        let cells: String = drift.positions[skipped..]
+            .iter()
+            .map(|p| {
+                format!(
+                    " {:>6}",
+                    p.map_or_else(|| "-".to_string(), |p| p.to_string())
+                )
+            })
+            .collect();
+        println!(
+            "{:<24}{} {:>10}",
+            value(&drift.item),
+            cells,
+            drift
+                .stability
+                .map_or_else(|| "-".to_string(), |s| format!("{:.2}", s))
+        );
+    }
+    if skipped > 0 {
+        println!(
+            "Showing the latest {} of {} rankings; stability covers them all.",
+            shown,
+            history.snapshots.len()
+        );
+    }
+    println!();
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rankhaus::{RankSet, User};
+    use rankhaus::session::ProbeKind;
+    use rankhaus::{RankSet, Ranking, User};
     use std::path::PathBuf;
 
     fn create_test_state() -> AppState {
@@ -251,6 +500,7 @@ mod tests {
             rankset: Some(rankset),
             active_user_id: None,
             active_strategy: "merge".to_string(),
+            active_strategy_config: None,
         }
     }
 
@@ -271,6 +521,58 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_stats() {
+        let mut state = create_test_state();
+        let rankset = state.rankset.as_mut().unwrap();
+        let user_id = rankset.get_user("alice").unwrap().id.clone();
+        let (a, b) = (Id::from("a"), Id::from("b"));
+
+        // Option 1 wins two of three comparisons and the one probe
+        let mut ranking = Ranking::new(user_id.clone(), "merge".to_string());
+        let session = &mut ranking.session;
+        session
+            .add_comparison(a.clone(), b.clone(), a.clone())
+            .shown_first = Some(a.clone());
+        session
+            .add_comparison(a.clone(), b.clone(), b.clone())
+            .shown_first = Some(b.clone());
+        session
+            .add_comparison(a.clone(), b.clone(), a.clone())
+            .shown_first = Some(b.clone());
+        session
+            .add_probe(
+                a.clone(),
+                b.clone(),
+                a.clone(),
+                b.clone(),
+                ProbeKind::Repeat,
+            )
+            .shown_first = Some(b.clone());
+        assert_eq!(
+            ranking.session.position_bias(),
+            PositionBias {
+                first: 3,
+                second: 1
+            }
+        );
+
+        // A second session adds to the user's total
+        let mut other = Ranking::new(user_id.clone(), "merge".to_string());
+        other
+            .session
+            .add_comparison(a.clone(), Id::from("c"), a.clone())
+            .shown_first = Some(Id::from("c"));
+        rankset.rankings.push(ranking);
+        rankset.rankings.push(other);
+        let bias = position_bias(rankset, &user_id);
+        assert_eq!((bias.first, bias.second), (3, 2));
+
+        assert!(stats(Some(&mut state), None).is_ok());
+        assert!(stats(Some(&mut state), Some("alice".to_string())).is_ok());
+        assert!(stats(Some(&mut state), Some("nobody".to_string())).is_err());
+    }
+
     #[test]
     fn test_list_no_state() {
         let result = list(None);
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 mod commands;
 mod repl;
@@ -40,14 +40,243 @@ pub enum Commands {
         command: StrategyCommands,
     },
 
+    /// Manage ranking criteria and combine their results
+    Criteria {
+        #[command(subcommand)]
+        command: CriteriaCommands,
+    },
+
     /// Perform ranking
-    Rank,
+    Rank(RankArgs),
 
     /// Manage ranking sessions
     Sessions {
         #[command(subcommand)]
         command: SessionsCommands,
     },
+
+    /// Combine completed rankings into one group order
+    Aggregate(AggregateArgs),
+
+    /// Show how closely two users, or two sessions, agree
+    Compare(CompareArgs),
+
+    /// Show how much every pair of users agrees, and groups of similar taste
+    Agreement(AgreementArgs),
+
+    /// Rate items from every user's comparisons pooled together
+    Leaderboard(LeaderboardArgs),
+
+    /// Run strategies against a simulated ranker to compare them
+    Simulate(SimulateArgs),
+}
+
+#[derive(Args)]
+pub struct AgreementArgs {
+    /// Compare rankings on this criterion (default: the default question)
+    #[arg(long, value_name = "NAME")]
+    pub criterion: Option<String>,
+
+    /// Only group users whose rankings agree at least this much on average,
+    /// as Kendall tau from -1 to 1
+    #[arg(
+        long,
+        value_name = "TAU",
+        default_value_t = 0.5,
+        allow_negative_numbers = true
+    )]
+    pub min_tau: f64,
+
+    /// Print the matrix and groups as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct LeaderboardArgs {
+    /// Rate items on this criterion (default: the default question)
+    #[arg(long, value_name = "NAME")]
+    pub criterion: Option<String>,
+
+    /// Print the ratings as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Args)]
+pub struct CompareArgs {
+    /// Username, user ID or session ID
+    pub first: String,
+
+    /// Username, user ID or session ID
+    pub second: String,
+
+    /// Compare users' rankings on this criterion (default: the default
+    /// question)
+    #[arg(long, value_name = "NAME")]
+    pub criterion: Option<String>,
+
+    /// How many disputed pairs to list
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    pub top: usize,
+}
+
+#[derive(Args)]
+pub struct AggregateArgs {
+    /// How to combine the rankings
+    #[arg(long, value_enum, default_value = "borda")]
+    pub method: AggregateMethod,
+
+    /// Only combine this user's rankings
+    #[arg(long)]
+    pub user: Option<String>,
+
+    /// Only combine rankings finished with this strategy
+    #[arg(long)]
+    pub strategy: Option<String>,
+
+    /// Combine rankings on this criterion (default: the default question)
+    #[arg(long, value_name = "NAME")]
+    pub criterion: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum AggregateMethod {
+    /// A point for every item placed below, summed over rankings
+    Borda,
+    /// A point for every item beaten head to head in most rankings
+    Copeland,
+    /// Mean position over the rankings
+    AverageRank,
+    /// Kemeny–Young: the order disagreeing with the fewest pairs across rankings
+    Kemeny,
+}
+
+#[derive(Args)]
+pub struct SimulateArgs {
+    /// Strategy to simulate (repeatable; default: all)
+    #[arg(long = "strategy", value_name = "NAME")]
+    pub strategies: Vec<String>,
+
+    /// Strategy option as KEY=VALUE, with a single --strategy (repeatable)
+    #[arg(long = "opt", value_name = "KEY=VALUE")]
+    pub options: Vec<String>,
+
+    /// Number of items to rank
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    pub items: usize,
+
+    /// Number of simulated rankings per strategy
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    pub runs: usize,
+
+    /// How the simulated ranker errs: none, flip:RATE (each answer wrong
+    /// with this probability) or close:SPREAD (close items mixed up more)
+    #[arg(long, value_name = "MODEL", default_value = "none")]
+    pub noise: String,
+
+    /// Seed, to reproduce a simulation exactly
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+}
+
+#[derive(Args)]
+pub struct RankArgs {
+    /// Criterion to rank the items on
+    #[arg(long, value_name = "NAME")]
+    pub criterion: Option<String>,
+
+    /// Only rank items with this tag (repeatable)
+    #[arg(long = "tag", value_name = "TAG")]
+    pub tags: Vec<String>,
+
+    /// Only rank items whose field or attribute matches, as KEY=VALUE or
+    /// KEY=LOW..HIGH (repeatable)
+    #[arg(long = "attr", value_name = "KEY=VALUE")]
+    pub attributes: Vec<String>,
+
+    /// Only rank these items (repeatable)
+    #[arg(long, value_name = "ITEM")]
+    pub only: Vec<String>,
+
+    /// Seed the strategy with a prior ordering, so the ranking only needs to
+    /// confirm differences
+    #[arg(long, value_enum)]
+    pub warm_start: Option<WarmStartFrom>,
+
+    /// Known preference that won't be asked, as "HIGHER>LOWER" (repeatable)
+    #[arg(long, value_name = "HIGHER>LOWER")]
+    pub above: Vec<String>,
+
+    /// Item that always ranks first
+    #[arg(long, value_name = "ITEM")]
+    pub first: Option<String>,
+
+    /// Item that always ranks last
+    #[arg(long, value_name = "ITEM")]
+    pub last: Option<String>,
+
+    /// Item fixed at a position, as "ITEM=POSITION" (repeatable)
+    #[arg(long, value_name = "ITEM=POSITION")]
+    pub fix: Vec<String>,
+
+    /// Drop each item into a tier instead of comparing pairs
+    #[arg(long, conflicts_with_all = ["first", "last", "fix"])]
+    pub tiers: bool,
+
+    /// Tier labels, best first (default: S,A,B,C,D)
+    #[arg(long, value_delimiter = ',', value_name = "LABELS", requires = "tiers")]
+    pub tier_labels: Vec<String>,
+
+    /// After tiering, order the items within each tier with the active strategy
+    #[arg(long, requires = "tiers")]
+    pub order_tiers: bool,
+
+    /// Score each item from 1 to 10 first, then compare only close scores
+    #[arg(long, conflicts_with = "tiers")]
+    pub scores: bool,
+
+    /// Compare items whose scores differ by at most this much
+    #[arg(long, value_name = "N", default_value_t = 1, requires = "scores")]
+    pub score_tolerance: u8,
+
+    /// Rank within each item group first, then combine the groups
+    #[arg(long, value_enum, value_name = "MODE", conflicts_with_all = ["tiers", "scores"])]
+    pub by_group: Option<GroupBy>,
+
+    /// Count answers slower than this many seconds as weak preferences
+    /// (rating strategies such as elo)
+    #[arg(long, value_name = "SECONDS")]
+    pub weak_after: Option<f64>,
+
+    /// Stop after this many comparisons and finish with the best estimate
+    #[arg(long, value_name = "N", conflicts_with = "by_group")]
+    pub max_comparisons: Option<usize>,
+
+    /// Stop after this many seconds of answering and finish with the best
+    /// estimate
+    #[arg(long, value_name = "SECONDS", conflicts_with = "by_group")]
+    pub time_limit: Option<u64>,
+
+    /// Seed for every random choice in the session, to reproduce it exactly
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum GroupBy {
+    /// Merge the group orders into one list
+    Merge,
+    /// Rank each group's best item, then list whole groups in that order
+    Representatives,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum WarmStartFrom {
+    /// Your own most recent completed ranking
+    Previous,
+    /// Consensus of all users' completed rankings
+    Consensus,
 }
 
 #[derive(Subcommand)]
@@ -72,6 +301,88 @@ pub enum ItemsCommands {
         /// New value
         new_value: String,
     },
+
+    /// Set or clear an item's group
+    Group {
+        /// Item identifier (ID or value)
+        identifier: String,
+
+        /// Group name. If omitted, the item's group is cleared.
+        group: Option<String>,
+    },
+
+    /// Show an item with all its details
+    Show {
+        /// Item identifier (ID or value)
+        identifier: String,
+    },
+
+    /// Show how each item ranks across users, its win rate and how
+    /// controversial it is
+    Stats {
+        /// Criterion to report on (default: the default question)
+        #[arg(long, value_name = "NAME")]
+        criterion: Option<String>,
+    },
+
+    /// Set an item's description, link, tags or attributes
+    Set {
+        /// Item identifier (ID or value)
+        identifier: String,
+
+        /// Description ("" clears it)
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Link to more information ("" clears it)
+        #[arg(long)]
+        url: Option<String>,
+
+        /// Tag to add (repeatable)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Tag to remove (repeatable)
+        #[arg(long = "untag", value_name = "TAG")]
+        untags: Vec<String>,
+
+        /// Attribute to set as KEY=VALUE, or KEY= to remove it (repeatable)
+        #[arg(long = "attr", value_name = "KEY=VALUE")]
+        attributes: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CriteriaCommands {
+    /// List all criteria
+    List,
+
+    /// Add a criterion
+    Add {
+        /// Criterion name, e.g. "price"
+        name: String,
+
+        /// Question shown when comparing items on this criterion
+        #[arg(long)]
+        question: Option<String>,
+    },
+
+    /// Remove a criterion (rankings on it are kept)
+    Remove {
+        /// Criterion name
+        name: String,
+    },
+
+    /// Blend the latest ranking on each criterion into an overall order
+    Combine {
+        /// Weight of a criterion, as "CRITERION=WEIGHT" (default 1, repeatable)
+        #[arg(long = "weight", value_name = "CRITERION=WEIGHT")]
+        weights: Vec<String>,
+
+        /// User whose rankings to combine (default: active user)
+        #[arg(long)]
+        user: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -117,6 +428,22 @@ pub enum UsersCommands {
         /// Username or user ID to set as default (omit to show current default)
         identifier: Option<String>,
     },
+
+    /// Show how users answer, such as how often they pick option 1
+    Stats {
+        /// Username or user ID (omit for all users)
+        identifier: Option<String>,
+    },
+
+    /// Show how a user's rankings changed over time
+    History {
+        /// Username or user ID (default: active user)
+        identifier: Option<String>,
+
+        /// Criterion to follow (default: the default question)
+        #[arg(long, value_name = "NAME")]
+        criterion: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -128,6 +455,11 @@ pub enum StrategyCommands {
     Select {
         /// Strategy name
         strategy: String,
+
+        /// Strategy option, e.g. "k_factor=16" for elo or "pivot=middle" for
+        /// quicksort (repeatable)
+        #[arg(long = "opt", value_name = "KEY=VALUE")]
+        options: Vec<String>,
     },
 }
 
@@ -159,6 +491,32 @@ pub enum RanksetsCommands {
         #[arg(long)]
         author: Option<String>,
     },
+
+    /// Show or change how comparisons are worded in the loaded rankset
+    Prompt {
+        /// Question asked for each comparison
+        #[arg(long)]
+        question: Option<String>,
+
+        /// Labels for the two options, e.g. "Left,Right" ("" clears them)
+        #[arg(long, value_delimiter = ',')]
+        labels: Option<Vec<String>>,
+
+        /// How to show items; {value}, {id} and {group} are replaced
+        #[arg(long)]
+        item_template: Option<String>,
+
+        /// Go back to the default wording
+        #[arg(long, conflicts_with_all = ["question", "labels", "item_template"])]
+        reset: bool,
+    },
+
+    /// Show or change whether finished sessions keep their comparisons
+    KeepComparisons {
+        /// on or off (omit to show the current setting)
+        #[arg(value_parser = clap::builder::BoolishValueParser::new())]
+        enabled: Option<bool>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -183,6 +541,19 @@ pub enum SessionsCommands {
         /// Session ID to resume
         session_id: String,
     },
+
+    /// Switch an in-progress session to another strategy and continue
+    Switch {
+        /// Session ID to switch
+        session_id: String,
+
+        /// Strategy to continue with
+        strategy: String,
+
+        /// Option for the new strategy (repeatable)
+        #[arg(long = "opt", value_name = "KEY=VALUE")]
+        options: Vec<String>,
+    },
 }
 
 fn main() -> Result<()> {
@@ -123,27 +123,92 @@ fn print_help() {
     println!("  ranksets list              List available ranksets");
     println!("  ranksets load <file>       Load an existing rankset");
     println!("  ranksets new <name>        Create a new ranking list");
+    println!("  ranksets prompt            Show or set the comparison wording");
+    println!("    --question <q>           Question asked for each comparison");
+    println!("    --labels <a,b>           Labels for the two options");
+    println!("    --item-template <t>      How items are shown, e.g. \"{{value}} ({{group}})\"");
+    println!("    --reset                  Back to the default wording");
+    println!("  ranksets keep-comparisons [on|off]  Keep comparisons of finished sessions");
     println!();
     println!("  items list                 List all items");
     println!("  items add [item]           Add item(s) (direct or interactive)");
     println!("  items remove               Remove items");
     println!("  items edit <id> <value>    Edit an item");
+    println!("  items group <id> [group]   Set or clear an item's group");
+    println!("  items show <id>            Show an item with its details");
+    println!("  items stats                Rank spread, win rate and controversy per item");
+    println!("  items set <id>             Set item details:");
+    println!("    --description <d> --url <u> --tag <t> --untag <t> --attr <key>=<value>");
     println!();
     println!("  users list                 List all users");
     println!("  users edit <user> <name>   Edit user display name");
     println!("  users select <username>    Select active user");
     println!("  users remove <username>    Remove a user");
+    println!("  users stats [username]     Show option 1 rate, answer times, hardest pairs");
+    println!("  users history [username]   Show how a user's rankings drifted over time");
     println!();
     println!("  strategies list            List available strategies");
     println!("  strategies select <name>   Select ranking strategy (current: merge)");
+    println!("    --opt <key=value>        Strategy option, e.g. k_factor=16 (repeatable)");
+    println!();
+    println!("  criteria list              List ranking criteria");
+    println!("  criteria add <name>        Add a criterion (--question to set the prompt)");
+    println!("  criteria remove <name>     Remove a criterion");
+    println!("  criteria combine           Blend your rankings on each criterion");
+    println!("    --weight <c>=<w>         Weight of a criterion (default 1, repeatable)");
     println!();
     println!("  rank                       Start new ranking session");
+    println!("    --criterion <name>       Rank on one criterion");
+    println!("    --tag <tag>              Only rank items with this tag (repeatable)");
+    println!("    --attr <key>=<value>     Only rank matching items; value may be LOW..HIGH");
+    println!("    --only <item>            Only rank these items (repeatable)");
+    println!("    --warm-start <from>      Seed with your previous ranking or consensus");
+    println!("    --above \"A>B\"            Declare a known preference (repeatable)");
+    println!("    --first/--last <item>    Pin an item to the top or bottom");
+    println!("    --fix <item>=<n>         Pin an item to position n (repeatable)");
+    println!("    --tiers                  Tier list mode (S/A/B/C/D)");
+    println!("    --tier-labels <a,b,..>   Custom tier labels, best first");
+    println!("    --order-tiers            Order items within each tier afterwards");
+    println!("    --scores                 Score items 1-10, then compare close scores");
+    println!("    --score-tolerance <n>    Compare scores at most n apart (default 1)");
+    println!(
+        "    --by-group <mode>        Rank within item groups, then merge or by representatives"
+    );
+    println!("    --weak-after <seconds>   Slower answers count as weak preferences (elo)");
+    println!("    --max-comparisons <n>    Finish early with a best estimate after n answers");
+    println!("    --time-limit <seconds>   Finish early after this much answering time");
+    println!("    --seed <n>               Reproduce a session's random choices exactly");
     println!("                             (Press 'q' during ranking to suspend)");
     println!();
     println!("  sessions list              List all ranking sessions");
     println!("  sessions resume <id>       Resume in-progress session");
+    println!("  sessions switch <id> <strategy>");
+    println!("                             Continue a session with another strategy");
+    println!("    --opt <key=value>        Option for the new strategy (repeatable)");
     println!("  sessions delete <id>       Delete a session");
     println!();
+    println!("  aggregate                  Combine completed rankings into a group order");
+    println!("    --method <name>          borda (default), copeland, average-rank or kemeny");
+    println!("    --user <user> --strategy <name> --criterion <name>");
+    println!("                             Only combine matching rankings");
+    println!();
+    println!("  compare <a> <b>            Agreement between two users or two sessions");
+    println!("    --criterion <name>       Compare users' rankings on a criterion");
+    println!("    --top <n>                Disputed pairs to list (default 5)");
+    println!();
+    println!("  agreement                  Agreement matrix and taste groups across users");
+    println!("    --min-tau <tau>          How alike a group must be (default 0.5)");
+    println!("    --criterion <name> --json");
+    println!();
+    println!("  leaderboard                Ratings from everyone's comparisons pooled");
+    println!("    --criterion <name> --json");
+    println!();
+    println!("  simulate                   Compare strategies against a simulated ranker");
+    println!("    --strategy <name>        Strategy to run (repeatable, default all)");
+    println!("    --items <n> --runs <n>   Items per ranking and rankings per strategy");
+    println!("    --noise <model>          none, flip:<rate> or close:<spread>");
+    println!("    --seed <n>               Reproduce a simulation exactly");
+    println!();
     println!("  help                       Show this help");
     println!("  exit                       Exit REPL (Ctrl+D also works)");
     println!();
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use rankhaus::strategy::StrategyConfig;
 use rankhaus::{Id, RankSet};
 use std::path::PathBuf;
 
@@ -8,6 +9,8 @@ pub struct AppState {
     pub active_user_id: Option<Id>,
     #[allow(dead_code)] // Will be used when strategy selection is implemented
     pub active_strategy: String,
+    /// Options for the active strategy; `None` uses its defaults
+    pub active_strategy_config: Option<StrategyConfig>,
 }
 
 impl AppState {
@@ -16,6 +19,7 @@ impl AppState {
             rankset: None,
             active_user_id: None,
             active_strategy: "merge".to_string(),
+            active_strategy_config: None,
         }
     }
 
@@ -0,0 +1,686 @@
+//! Combining many completed rankings into one group order
+//!
+//! Each method turns the rankings into a score per item and orders the
+//! items by it. Only full orders of all items on the same question are
+//! combined: rankings of a filtered subset, rankings stopped early by a
+//! budget and unordered tier lists are left out. An item added or removed
+//! since a ranking only counts in the rankings that include it.
+
+use crate::{Error, Id, RankSet, Ranking, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// How rankings are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Method {
+    /// Each ranking gives an item a point for every item it placed below it
+    Borda,
+    /// An item scores a point for every item a majority of rankings placed
+    /// below it, and half a point for an even split
+    Copeland,
+    /// Items are ordered by their mean position, best first
+    AverageRank,
+    /// Kemeny–Young: the order that disagrees with the fewest pairs across
+    /// all rankings
+    Kemeny,
+}
+
+impl Method {
+    /// One-line explanation of how the method scores items
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Method::Borda => "a point for every item placed below it, summed over rankings",
+            Method::Copeland => {
+                "a point for every item it beats head to head in most rankings, half for a split"
+            }
+            Method::AverageRank => "mean position over the rankings that include it",
+            Method::Kemeny => "disagreements with the rankings over pairs including it",
+        }
+    }
+
+    /// Whether a higher score is better
+    fn higher_is_better(&self) -> bool {
+        !matches!(self, Method::AverageRank)
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Borda => write!(f, "Borda count"),
+            Method::Copeland => write!(f, "Copeland"),
+            Method::AverageRank => write!(f, "average rank"),
+            Method::Kemeny => write!(f, "Kemeny–Young"),
+        }
+    }
+}
+
+/// Which completed rankings to combine
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    /// Only this user's rankings
+    pub user: Option<Id>,
+    /// Only rankings finished with this strategy
+    pub strategy: Option<String>,
+    /// Criterion the rankings answer; `None` for the default question
+    pub criterion: Option<String>,
+}
+
+impl Selection {
+    /// The completed rankings of all items in `rankset` this selection
+    /// picks, each a strict order
+    pub fn rankings<'a>(&self, rankset: &'a RankSet) -> Vec<&'a Ranking> {
+        rankset
+            .rankings
+            .iter()
+            .filter(|r| r.is_strict_order())
+            .filter(|r| r.in_scope(self.criterion.as_deref(), None))
+            .filter(|r| self.user.as_ref().is_none_or(|user| &r.user_id == user))
+            .filter(|r| self.strategy.as_ref().is_none_or(|s| &r.strategy == s))
+            .collect()
+    }
+}
+
+/// An item's place in the group order
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    pub item: Id,
+    /// Score under the method used
+    pub score: f64,
+    /// Mean 1-based position over the rankings that include the item
+    pub average_rank: f64,
+    /// How many rankings include the item
+    pub rankings: usize,
+}
+
+/// What decided the order of two items with the same score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreaker {
+    /// The item with the better average rank goes first
+    AverageRank,
+    /// Nothing separated them, so the lower ID goes first
+    Id,
+}
+
+/// Two neighbouring items that tied on score
+///
+/// For Kemeny–Young they tie when the rankings split evenly on them, so
+/// either order disagrees equally.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TieBreak {
+    pub above: Id,
+    pub below: Id,
+    /// The score both had; for Kemeny–Young, how many rankings placed each
+    /// above the other
+    pub score: f64,
+    pub by: TieBreaker,
+}
+
+/// A group order and how it was reached
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Aggregate {
+    pub method: Method,
+    /// Number of rankings combined
+    pub rankings: usize,
+    /// Pairs of items the group order puts the other way round from a
+    /// ranking, summed over rankings
+    pub disagreement: usize,
+    /// Whether no order has fewer disagreements; only known for
+    /// Kemeny–Young over at most [`KEMENY_EXACT_LIMIT`] items
+    pub optimal: bool,
+    /// Items best first
+    pub standings: Vec<Standing>,
+    pub tie_breaks: Vec<TieBreak>,
+}
+
+/// Most items Kemeny–Young orders exactly; larger sets are ordered by
+/// local search from the Borda order, which may not find the best order
+pub const KEMENY_EXACT_LIMIT: usize = 14;
+
+/// Combine the completed rankings `selection` picks from `rankset`
+///
+/// Items no longer in the rankset are ignored.
+pub fn aggregate(rankset: &RankSet, method: Method, selection: &Selection) -> Result<Aggregate> {
+    let orders = current_orders(rankset, selection.rankings(rankset));
+    if orders.is_empty() {
+        return Err(Error::Other(
+            "No completed rankings to aggregate".to_string(),
+        ));
+    }
+
+    let orders: Vec<&[Id]> = orders.iter().map(|o| o.as_slice()).collect();
+    Ok(aggregate_orders(&orders, method))
+}
+
+/// The rankings' orders restricted to items still in `rankset`, leaving out
+/// any that end up empty
+pub(crate) fn current_orders<'a>(
+    rankset: &RankSet,
+    rankings: impl IntoIterator<Item = &'a Ranking>,
+) -> Vec<Vec<Id>> {
+    rankings
+        .into_iter()
+        .filter_map(|r| r.result.as_ref().and_then(|result| result.order.as_ref()))
+        .map(|order| {
+            order
+                .iter()
+                .filter(|id| rankset.items.contains_key(id.as_str()))
+                .cloned()
+                .collect::<Vec<Id>>()
+        })
+        .filter(|order| !order.is_empty())
+        .collect()
+}
+
+/// Combine orders, each listing items best first
+pub fn aggregate_orders(orders: &[&[Id]], method: Method) -> Aggregate {
+    // Positions of each item, and how many items each order placed below it
+    let mut positions: HashMap<&Id, Vec<usize>> = HashMap::new();
+    let mut borda: HashMap<&Id, f64> = HashMap::new();
+    for order in orders {
+        for (position, id) in order.iter().enumerate() {
+            positions.entry(id).or_default().push(position + 1);
+            *borda.entry(id).or_default() += (order.len() - position - 1) as f64;
+        }
+    }
+
+    let items: Vec<&Id> = positions.keys().copied().collect();
+    let placed: Vec<HashMap<&Id, usize>> = orders
+        .iter()
+        .map(|order| order.iter().enumerate().map(|(i, id)| (id, i)).collect())
+        .collect();
+
+    let score = |id: &Id| -> f64 {
+        match method {
+            // Kemeny–Young searches from the Borda order
+            Method::Borda | Method::Kemeny => borda[id],
+            Method::Copeland => copeland(&placed, id, &items),
+            Method::AverageRank => mean(&positions[id]),
+        }
+    };
+
+    let mut standings: Vec<Standing> = positions
+        .iter()
+        .map(|(id, p)| Standing {
+            item: (*id).clone(),
+            score: score(id),
+            average_rank: mean(p),
+            rankings: p.len(),
+        })
+        .collect();
+
+    standings.sort_by(|a, b| {
+        let by_score = if method.higher_is_better() {
+            b.score.total_cmp(&a.score)
+        } else {
+            a.score.total_cmp(&b.score)
+        };
+        by_score
+            .then_with(|| a.average_rank.total_cmp(&b.average_rank))
+            .then_with(|| a.item.cmp(&b.item))
+    });
+
+    // From here on items are indexes into `standings`
+    let above = pairwise(&placed, &standings);
+    let mut order: Vec<usize> = (0..standings.len()).collect();
+    let optimal = method == Method::Kemeny && order.len() <= KEMENY_EXACT_LIMIT;
+    if method == Method::Kemeny {
+        if optimal {
+            order = kemeny_exact(&above);
+        } else {
+            kemeny_local_search(&above, &mut order);
+        }
+        settle_ties(&mut order, &above, &standings);
+        for (position, &i) in order.iter().enumerate() {
+            standings[i].score = order
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != position)
+                .map(|(other, &j)| {
+                    if position < other {
+                        above[j][i]
+                    } else {
+                        above[i][j]
+                    }
+                })
+                .sum::<usize>() as f64;
+        }
+    }
+
+    let tie_breaks = order
+        .windows(2)
+        .filter_map(|pair| {
+            let (a, b) = (&standings[pair[0]], &standings[pair[1]]);
+            let score = if method == Method::Kemeny {
+                let split = above[pair[0]][pair[1]];
+                (split == above[pair[1]][pair[0]]).then_some(split as f64)?
+            } else {
+                same(a.score, b.score).then_some(a.score)?
+            };
+            Some(TieBreak {
+                above: a.item.clone(),
+                below: b.item.clone(),
+                score,
+                by: if same(a.average_rank, b.average_rank) {
+                    TieBreaker::Id
+                } else {
+                    TieBreaker::AverageRank
+                },
+            })
+        })
+        .collect();
+
+    let disagreement = cost(&above, &order);
+    let mut standings: Vec<Option<Standing>> = standings.into_iter().map(Some).collect();
+    let standings = order.iter().filter_map(|&i| standings[i].take()).collect();
+
+    Aggregate {
+        method,
+        rankings: orders.len(),
+        disagreement,
+        optimal,
+        standings,
+        tie_breaks,
+    }
+}
+
+/// How many orders place each item above each other item, indexed like
+/// `standings`
+fn pairwise(placed: &[HashMap<&Id, usize>], standings: &[Standing]) -> Vec<Vec<usize>> {
+    let n = standings.len();
+    let mut above = vec![vec![0; n]; n];
+    for order in placed {
+        let positions: Vec<Option<usize>> = standings
+            .iter()
+            .map(|s| order.get(&s.item).copied())
+            .collect();
+        for i in 0..n {
+            for j in 0..n {
+                if let (Some(a), Some(b)) = (positions[i], positions[j]) {
+                    if a < b {
+                        above[i][j] += 1;
+                    }
+                }
+            }
+        }
+    }
+    above
+}
+
+/// Pairwise disagreements between `order` and the orders counted in `above`
+fn cost(above: &[Vec<usize>], order: &[usize]) -> usize {
+    order
+        .iter()
+        .enumerate()
+        .flat_map(|(p, &i)| order[p + 1..].iter().map(move |&j| above[j][i]))
+        .sum()
+}
+
+/// The order with the fewest disagreements, by dynamic programming over
+/// the sets of items placed first
+fn kemeny_exact(above: &[Vec<usize>]) -> Vec<usize> {
+    let n = above.len();
+    let full = 1usize << n;
+    // Fewest disagreements among the items of each set when they come
+    // first, and which of them comes last
+    let mut best = vec![usize::MAX; full];
+    let mut last = vec![0u8; full];
+    best[0] = 0;
+
+    for set in 0..full {
+        if best[set] == usize::MAX {
+            continue;
+        }
+        for x in (0..n).filter(|x| set & (1 << x) == 0) {
+            // Every order preferring x to an item already placed disagrees
+            let added: usize = (0..n)
+                .filter(|y| set & (1 << y) != 0)
+                .map(|y| above[x][y])
+                .sum();
+            let next = set | (1 << x);
+            if best[set] + added < best[next] {
+                best[next] = best[set] + added;
+                last[next] = x as u8;
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(n);
+    let mut set = full - 1;
+    while set != 0 {
+        let x = last[set] as usize;
+        order.push(x);
+        set &= !(1 << x);
+    }
+    order.reverse();
+    order
+}
+
+/// Improve `order` by moving single items to wherever they cause the
+/// fewest disagreements, until no move helps
+fn kemeny_local_search(above: &[Vec<usize>], order: &mut Vec<usize>) {
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for from in 0..order.len() {
+            let x = order[from];
+            // Change in disagreements from moving x to each position
+            let (mut best_to, mut best_delta) = (from, 0i64);
+            let mut delta = 0i64;
+            for to in (0..from).rev() {
+                let y = order[to];
+                delta += above[y][x] as i64 - above[x][y] as i64;
+                if delta < best_delta {
+                    (best_to, best_delta) = (to, delta);
+                }
+            }
+            delta = 0;
+            for (to, &y) in order.iter().enumerate().skip(from + 1) {
+                delta += above[x][y] as i64 - above[y][x] as i64;
+                if delta < best_delta {
+                    (best_to, best_delta) = (to, delta);
+                }
+            }
+            if best_to != from {
+                order.remove(from);
+                order.insert(best_to, x);
+                improved = true;
+            }
+        }
+    }
+}
+
+/// Order neighbours the rankings split evenly on by average rank, then ID
+///
+/// Swapping such neighbours doesn't change the disagreement count.
+fn settle_ties(order: &mut [usize], above: &[Vec<usize>], standings: &[Standing]) {
+    let goes_first = |a: &Standing, b: &Standing| {
+        a.average_rank
+            .total_cmp(&b.average_rank)
+            .then_with(|| a.item.cmp(&b.item))
+            .is_lt()
+    };
+    let mut swapped = true;
+    while swapped {
+        swapped = false;
+        for k in 0..order.len().saturating_sub(1) {
+            let (a, b) = (order[k], order[k + 1]);
+            if above[a][b] == above[b][a] && goes_first(&standings[b], &standings[a]) {
+                order.swap(k, k + 1);
+                swapped = true;
+            }
+        }
+    }
+}
+
+/// Copeland score of `id` against every other item, given each order's
+/// positions
+fn copeland(placed: &[HashMap<&Id, usize>], id: &Id, items: &[&Id]) -> f64 {
+    let mut score = 0.0;
+    for other in items.iter().filter(|other| **other != id) {
+        let (mut wins, mut losses) = (0, 0);
+        for order in placed {
+            if let (Some(a), Some(b)) = (order.get(id), order.get(*other)) {
+                if a < b {
+                    wins += 1;
+                } else {
+                    losses += 1;
+                }
+            }
+        }
+        if wins > losses {
+            score += 1.0;
+        } else if wins == losses && wins > 0 {
+            score += 0.5;
+        }
+    }
+    score
+}
+
+fn mean(values: &[usize]) -> f64 {
+    values.iter().sum::<usize>() as f64 / values.len() as f64
+}
+
+fn same(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(|n| Id::from(*n)).collect()
+    }
+
+    fn order(aggregate: &Aggregate) -> Vec<Id> {
+        aggregate.standings.iter().map(|s| s.item.clone()).collect()
+    }
+
+    #[test]
+    fn test_methods() {
+        let first = ids(&["a", "b", "c"]);
+        let second = ids(&["a", "c", "b"]);
+        let third = ids(&["b", "a", "c"]);
+        let orders = [first.as_slice(), second.as_slice(), third.as_slice()];
+
+        let borda = aggregate_orders(&orders, Method::Borda);
+        assert_eq!(order(&borda), ids(&["a", "b", "c"]));
+        assert_eq!(borda.standings[0].score, 5.0);
+        assert_eq!(borda.rankings, 3);
+
+        let copeland = aggregate_orders(&orders, Method::Copeland);
+        assert_eq!(order(&copeland), ids(&["a", "b", "c"]));
+        assert_eq!(copeland.standings[0].score, 2.0);
+
+        let average = aggregate_orders(&orders, Method::AverageRank);
+        assert_eq!(order(&average), ids(&["a", "b", "c"]));
+        assert!((average.standings[0].score - 4.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_tie_breaks() {
+        // b, c and d each beat one other item head to head. d has the
+        // best average rank; b and c are level on that too.
+        let first = ids(&["a", "b", "c", "d"]);
+        let second = ids(&["a", "c", "d", "b"]);
+        let third = ids(&["d", "a", "b", "c"]);
+        let orders = [first.as_slice(), second.as_slice(), third.as_slice()];
+
+        let copeland = aggregate_orders(&orders, Method::Copeland);
+        assert_eq!(order(&copeland), ids(&["a", "d", "b", "c"]));
+        let tie_break = |above: &str, below: &str, by| TieBreak {
+            above: Id::from(above),
+            below: Id::from(below),
+            score: 1.0,
+            by,
+        };
+        assert_eq!(
+            copeland.tie_breaks,
+            vec![
+                tie_break("d", "b", TieBreaker::AverageRank),
+                tie_break("b", "c", TieBreaker::Id),
+            ]
+        );
+
+        // Mirror images tie on everything, so IDs decide
+        let reversed = ids(&["d", "c", "b", "a"]);
+        let orders = [first.as_slice(), reversed.as_slice()];
+        let borda = aggregate_orders(&orders, Method::Borda);
+        assert_eq!(order(&borda), ids(&["a", "b", "c", "d"]));
+        assert_eq!(borda.tie_breaks.len(), 3);
+        assert!(borda.tie_breaks.iter().all(|t| t.by == TieBreaker::Id));
+    }
+
+    fn permutations(items: Vec<usize>) -> Vec<Vec<usize>> {
+        if items.len() <= 1 {
+            return vec![items];
+        }
+        let mut all = Vec::new();
+        for (i, &first) in items.iter().enumerate() {
+            let mut rest = items.clone();
+            rest.remove(i);
+            for mut tail in permutations(rest) {
+                tail.insert(0, first);
+                all.push(tail);
+            }
+        }
+        all
+    }
+
+    #[test]
+    fn test_kemeny_finds_fewest_disagreements() {
+        let first = ids(&["a", "b", "c", "d", "e", "f"]);
+        let second = ids(&["c", "a", "b", "f", "e", "d"]);
+        let third = ids(&["b", "c", "a", "d", "f", "e"]);
+        let fourth = ids(&["a", "c", "e", "b", "d", "f"]);
+        let orders = [
+            first.as_slice(),
+            second.as_slice(),
+            third.as_slice(),
+            fourth.as_slice(),
+        ];
+
+        let kemeny = aggregate_orders(&orders, Method::Kemeny);
+        assert!(kemeny.optimal);
+
+        // Checking every order finds nothing better
+        let placed: Vec<HashMap<&Id, usize>> = orders
+            .iter()
+            .map(|order| order.iter().enumerate().map(|(i, id)| (id, i)).collect())
+            .collect();
+        let above = pairwise(&placed, &kemeny.standings);
+        let fewest = permutations((0..6).collect())
+            .iter()
+            .map(|order| cost(&above, order))
+            .min()
+            .unwrap();
+        assert_eq!(kemeny.disagreement, fewest);
+        assert_eq!(cost(&above, &(0..6).collect::<Vec<_>>()), fewest);
+
+        // Each pair disagrees from both items' side
+        let total: f64 = kemeny.standings.iter().map(|s| s.score).sum();
+        assert_eq!(total, 2.0 * fewest as f64);
+
+        let borda = aggregate_orders(&orders, Method::Borda);
+        assert!(!borda.optimal);
+        assert!(borda.disagreement >= kemeny.disagreement);
+    }
+
+    #[test]
+    fn test_kemeny_local_search() {
+        // Too many items to solve exactly; two rankings swap neighbours
+        let names: Vec<String> = (0..20).map(|i| format!("i{:02}", i)).collect();
+        let truth: Vec<Id> = names.iter().map(|n| Id::from(n.as_str())).collect();
+        let mut swapped = truth.clone();
+        swapped.swap(3, 4);
+        let mut other = truth.clone();
+        other.swap(10, 11);
+        let orders = [truth.as_slice(), swapped.as_slice(), other.as_slice()];
+
+        let kemeny = aggregate_orders(&orders, Method::Kemeny);
+        assert!(!kemeny.optimal);
+        assert_eq!(order(&kemeny), truth);
+        assert_eq!(kemeny.disagreement, 2);
+
+        // Reversing the order first still ends at the best order
+        let mut reversed: Vec<usize> = (0..20).rev().collect();
+        let placed: Vec<HashMap<&Id, usize>> = orders
+            .iter()
+            .map(|order| order.iter().enumerate().map(|(i, id)| (id, i)).collect())
+            .collect();
+        let above = pairwise(&placed, &kemeny.standings);
+        kemeny_local_search(&above, &mut reversed);
+        assert_eq!(reversed, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_kemeny_ties() {
+        let first = ids(&["a", "b", "c"]);
+        let second = ids(&["c", "b", "a"]);
+        let orders = [first.as_slice(), second.as_slice()];
+
+        // Every pair splits evenly, so every order disagrees three times
+        let kemeny = aggregate_orders(&orders, Method::Kemeny);
+        assert_eq!(kemeny.disagreement, 3);
+        assert_eq!(order(&kemeny), ids(&["a", "b", "c"]));
+        assert_eq!(kemeny.tie_breaks.len(), 2);
+        assert!(kemeny.tie_breaks.iter().all(|t| t.by == TieBreaker::Id));
+        assert_eq!(kemeny.tie_breaks[0].score, 1.0);
+    }
+
+    #[test]
+    fn test_selection_takes_full_strict_orders() {
+        use crate::filter::ItemFilter;
+        use crate::ranking::completed_ranking;
+        use crate::tier::{Tier, TierList};
+        use crate::{Item, User};
+
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        for name in ["a", "b", "c"] {
+            let item = Item::with_id(Id::from(name), name.to_string(), chrono::Utc::now());
+            rankset.add_item(item).unwrap();
+        }
+        let user = User::new("alice".to_string(), None);
+        let user_id = user.id.clone();
+        rankset.add_user(user).unwrap();
+        let full = completed_ranking(&user_id, &ids(&["a", "b", "c"]));
+
+        let mut subset = completed_ranking(&user_id, &ids(&["c", "a"]));
+        subset.filter = Some(ItemFilter::new(
+            vec![],
+            Default::default(),
+            ids(&["a", "c"]),
+        ));
+
+        let mut stopped = completed_ranking(&user_id, &ids(&["c", "b", "a"]));
+        stopped.result.as_mut().unwrap().confidence = Some(0.6);
+
+        let tiered = |order_within| {
+            let mut ranking = completed_ranking(&user_id, &ids(&["b", "a", "c"]));
+            ranking.tier_list = Some(TierList::new(vec!["S".to_string()], order_within).unwrap());
+            ranking.result.as_mut().unwrap().tiers = Some(vec![Tier {
+                label: "S".to_string(),
+                items: ids(&["b", "a", "c"]),
+            }]);
+            ranking
+        };
+
+        rankset.rankings = vec![full, subset, stopped, tiered(false), tiered(true)];
+        let picked: Vec<_> = Selection::default()
+            .rankings(&rankset)
+            .iter()
+            .map(|r| r.result.as_ref().unwrap().order.clone().unwrap())
+            .collect();
+        assert_eq!(picked, vec![ids(&["a", "b", "c"]), ids(&["b", "a", "c"])]);
+    }
+
+    #[test]
+    fn test_partial_rankings() {
+        // d is only in one ranking, so only counts there
+        let full = ids(&["a", "b", "c"]);
+        let subset = ids(&["d", "a"]);
+        let orders = [full.as_slice(), subset.as_slice()];
+
+        let average = aggregate_orders(&orders, Method::AverageRank);
+        let d = average
+            .standings
+            .iter()
+            .find(|s| s.item.as_str() == "d")
+            .unwrap();
+        assert_eq!(d.rankings, 1);
+        assert_eq!(d.average_rank, 1.0);
+
+        let copeland = aggregate_orders(&orders, Method::Copeland);
+        // d beats a and never meets b or c
+        let d = copeland
+            .standings
+            .iter()
+            .find(|s| s.item.as_str() == "d")
+            .unwrap();
+        assert_eq!(d.score, 1.0);
+    }
+}
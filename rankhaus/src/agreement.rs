@@ -0,0 +1,179 @@
+//! How much users agree with each other, and which of them rank alike
+//!
+//! Users are compared by Kendall's tau between their latest completed
+//! rankings. Groups of like-minded users are found by average-linkage
+//! hierarchical clustering: starting from one group per user, the two
+//! groups whose members agree most on average are joined, until no two
+//! groups agree enough.
+
+use crate::{correlation, Error, Id, RankSet, Result};
+use serde::Serialize;
+
+/// Agreement between every pair of users, and the groups they fall into
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Agreement {
+    /// Users with a completed ranking, listed group by group
+    pub users: Vec<Id>,
+    /// Kendall's tau between each pair of users, indexed like `users`
+    pub kendall_tau: Vec<Vec<f64>>,
+    /// Groups of users who rank alike, largest first
+    pub clusters: Vec<Cluster>,
+}
+
+/// Users who rank alike
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Cluster {
+    pub users: Vec<Id>,
+    /// Mean Kendall's tau between members; 1 for a single user
+    pub cohesion: f64,
+}
+
+/// Compare the latest completed ranking of every user on `criterion`
+/// (`None` for the default question) over all items
+///
+/// Groups are only joined while their members' mean Kendall's tau is at
+/// least `min_tau`.
+pub fn agreement(rankset: &RankSet, criterion: Option<&str>, min_tau: f64) -> Result<Agreement> {
+    let mut rankers: Vec<(Id, &[Id])> = rankset
+        .users
+        .values()
+        .filter_map(|user| {
+            let order = rankset
+                .latest_ranking(&user.id, criterion, None)?
+                .result
+                .as_ref()?
+                .order
+                .as_deref()?;
+            Some((user.id.clone(), order))
+        })
+        .collect();
+    rankers.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if rankers.len() < 2 {
+        return Err(Error::Other(
+            "At least 2 users need a completed ranking to compare".to_string(),
+        ));
+    }
+    Ok(agreement_of(&rankers, min_tau))
+}
+
+/// Agreement between users, each with an order of items best first
+pub fn agreement_of(rankers: &[(Id, &[Id])], min_tau: f64) -> Agreement {
+    let n = rankers.len();
+    let tau: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| correlation::kendall_tau(rankers[i].1, rankers[j].1))
+                .collect()
+        })
+        .collect();
+
+    let mut groups = cluster(&tau, min_tau);
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+
+    // List users group by group so like-minded users sit together
+    let order: Vec<usize> = groups.iter().flatten().copied().collect();
+    Agreement {
+        users: order.iter().map(|&i| rankers[i].0.clone()).collect(),
+        kendall_tau: order
+            .iter()
+            .map(|&i| order.iter().map(|&j| tau[i][j]).collect())
+            .collect(),
+        clusters: groups
+            .iter()
+            .map(|group| Cluster {
+                users: group.iter().map(|&i| rankers[i].0.clone()).collect(),
+                cohesion: if group.len() < 2 {
+                    1.0
+                } else {
+                    linkage(&tau, group, group)
+                },
+            })
+            .collect(),
+    }
+}
+
+/// Average-linkage clustering of users, as indexes into `tau`
+fn cluster(tau: &[Vec<f64>], min_tau: f64) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = (0..tau.len()).map(|i| vec![i]).collect();
+
+    loop {
+        let mut closest: Option<(usize, usize, f64)> = None;
+        for a in 0..groups.len() {
+            for b in a + 1..groups.len() {
+                let linked = linkage(tau, &groups[a], &groups[b]);
+                if linked >= min_tau && closest.is_none_or(|(_, _, best)| linked > best) {
+                    closest = Some((a, b, linked));
+                }
+            }
+        }
+
+        let Some((a, b, _)) = closest else {
+            return groups;
+        };
+        let joined = groups.remove(b);
+        groups[a].extend(joined);
+        groups[a].sort();
+    }
+}
+
+/// Mean tau between members of `a` and members of `b`, not counting anyone
+/// against themselves
+fn linkage(tau: &[Vec<f64>], a: &[usize], b: &[usize]) -> f64 {
+    let pairs: Vec<f64> = a
+        .iter()
+        .flat_map(|&i| b.iter().filter(move |&&j| j != i).map(move |&j| tau[i][j]))
+        .collect();
+    pairs.iter().sum::<f64>() / pairs.len().max(1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(|n| Id::from(*n)).collect()
+    }
+
+    #[test]
+    fn test_agreement_groups_alike_users() {
+        let sweet = ids(&["a", "b", "c", "d", "e"]);
+        let sweeter = ids(&["b", "a", "c", "d", "e"]);
+        let sour = ids(&["e", "d", "c", "b", "a"]);
+        let sourer = ids(&["e", "d", "c", "a", "b"]);
+        let odd = ids(&["c", "a", "e", "b", "d"]);
+        let rankers: Vec<(Id, &[Id])> = vec![
+            (Id::from("u1"), &sweet),
+            (Id::from("u2"), &sour),
+            (Id::from("u3"), &sweeter),
+            (Id::from("u4"), &sourer),
+            (Id::from("u5"), &odd),
+        ];
+
+        let result = agreement_of(&rankers, 0.5);
+        let groups: Vec<Vec<Id>> = result.clusters.iter().map(|c| c.users.clone()).collect();
+        assert_eq!(
+            groups,
+            vec![ids(&["u1", "u3"]), ids(&["u2", "u4"]), ids(&["u5"])]
+        );
+        assert!((result.clusters[0].cohesion - 0.8).abs() < 1e-9);
+        assert_eq!(result.clusters[2].cohesion, 1.0);
+
+        // The matrix follows the grouped order
+        assert_eq!(result.users, ids(&["u1", "u3", "u2", "u4", "u5"]));
+        assert_eq!(result.kendall_tau[0][0], 1.0);
+        assert!((result.kendall_tau[0][1] - 0.8).abs() < 1e-9);
+        assert_eq!(result.kendall_tau[0][2], -1.0);
+    }
+
+    #[test]
+    fn test_agreement_threshold() {
+        let first = ids(&["a", "b", "c"]);
+        let second = ids(&["a", "c", "b"]);
+        let rankers: Vec<(Id, &[Id])> = vec![(Id::from("u1"), &first), (Id::from("u2"), &second)];
+
+        // tau is 1/3
+        assert_eq!(agreement_of(&rankers, 0.5).clusters.len(), 2);
+        assert_eq!(agreement_of(&rankers, 0.3).clusters.len(), 1);
+    }
+}
@@ -0,0 +1,111 @@
+//! Capping how much a ranking session asks
+//!
+//! A budgeted ranking stops once it has used its comparisons or its time,
+//! and finishes with the strategy's best estimate of the order so far.
+
+use crate::Session;
+use serde::{Deserialize, Serialize};
+
+/// Limits on a ranking session; either or both may be set
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Budget {
+    /// Most comparisons to answer, counting resumed ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_comparisons: Option<usize>,
+    /// Most time to spend answering, in seconds
+    ///
+    /// Only time spent on questions counts, so a suspended session doesn't
+    /// use up its time while it waits to be resumed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_limit_secs: Option<u64>,
+}
+
+impl Budget {
+    /// Whether the session has used up the budget
+    pub fn is_spent(&self, session: &Session) -> bool {
+        let comparisons_spent = self
+            .max_comparisons
+            .is_some_and(|max| session.comparisons.len() >= max);
+        let time_spent = self
+            .time_limit_secs
+            .is_some_and(|limit| answer_time_ms(session) >= limit * 1000);
+        comparisons_spent || time_spent
+    }
+
+    /// Short description, e.g. "20 comparisons or 5m 0s"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(max) = self.max_comparisons {
+            parts.push(format!(
+                "{} comparison{}",
+                max,
+                if max == 1 { "" } else { "s" }
+            ));
+        }
+        if let Some(secs) = self.time_limit_secs {
+            parts.push(if secs < 60 {
+                format!("{}s", secs)
+            } else {
+                format!("{}m {}s", secs / 60, secs % 60)
+            });
+        }
+        parts.join(" or ")
+    }
+}
+
+/// Time spent answering the session's comparisons so far
+pub fn answer_time_ms(session: &Session) -> u64 {
+    session
+        .comparisons
+        .iter()
+        .filter_map(|c| c.latency_ms)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Id;
+
+    fn session(times: &[u64]) -> Session {
+        let mut session = Session::new();
+        for ms in times {
+            let comparison = session.add_comparison(Id::from("a"), Id::from("b"), Id::from("a"));
+            comparison.latency_ms = Some(*ms);
+        }
+        session
+    }
+
+    #[test]
+    fn test_is_spent() {
+        let by_count = Budget {
+            max_comparisons: Some(3),
+            time_limit_secs: None,
+        };
+        assert!(!by_count.is_spent(&session(&[1000, 1000])));
+        assert!(by_count.is_spent(&session(&[1000, 1000, 1000])));
+
+        let by_time = Budget {
+            max_comparisons: None,
+            time_limit_secs: Some(5),
+        };
+        assert!(!by_time.is_spent(&session(&[2000, 2000])));
+        assert!(by_time.is_spent(&session(&[2000, 3000])));
+
+        assert!(!Budget::default().is_spent(&session(&[60_000; 10])));
+    }
+
+    #[test]
+    fn test_describe() {
+        let budget = Budget {
+            max_comparisons: Some(20),
+            time_limit_secs: Some(300),
+        };
+        assert_eq!(budget.describe(), "20 comparisons or 5m 0s");
+        let budget = Budget {
+            max_comparisons: Some(1),
+            time_limit_secs: None,
+        };
+        assert_eq!(budget.describe(), "1 comparison");
+    }
+}
@@ -0,0 +1,327 @@
+//! Consistency checks over recorded comparisons
+//!
+//! Strategies cache every answer, so a ranker answering at random still
+//! produces a complete ranking. These helpers pick probe pairs to re-ask
+//! and find preference cycles in a session's comparisons.
+
+use crate::session::{Comparison, ProbeKind};
+use crate::Id;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// A pair chosen to check whether a ranker answers consistently
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeCandidate {
+    pub a: Id,
+    pub b: Id,
+    /// Winner implied by earlier comparisons
+    pub expected: Id,
+    pub kind: ProbeKind,
+}
+
+/// Pick a pair to re-ask as a consistency probe
+///
+/// Prefers a pair that was never asked but whose answer is implied by
+/// transitivity (a beat b, b beat c, so a should beat c). Falls back to
+/// repeating an earlier pair. Returns `None` if nothing has been compared.
+pub fn select_probe<R: Rng + ?Sized>(
+    comparisons: &[Comparison],
+    rng: &mut R,
+) -> Option<ProbeCandidate> {
+    let answers = latest_answers(comparisons);
+    if answers.is_empty() {
+        return None;
+    }
+
+    let beats = winner_graph(&answers);
+
+    // Pairs implied by a chain of two answers but never asked directly
+    let mut implied = BTreeSet::new();
+    for (x, middle) in &beats {
+        for y in middle {
+            if let Some(losers) = beats.get(y) {
+                for z in losers {
+                    if z != x && !answers.contains_key(&pair_key(x, z)) {
+                        implied.insert((x.clone(), z.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    let (winner, loser, kind) = if implied.is_empty() {
+        let pairs: Vec<_> = answers.iter().collect();
+        let ((a, b), winner) = pairs.choose(rng)?;
+        let loser = if *winner == a { b } else { a };
+        ((*winner).clone(), loser.clone(), ProbeKind::Repeat)
+    } else {
+        let implied: Vec<_> = implied.into_iter().collect();
+        let (winner, loser) = implied.choose(rng)?.clone();
+        (winner, loser, ProbeKind::Transitive)
+    };
+
+    // Don't always show the expected winner first
+    let (a, b) = if rng.random_bool(0.5) {
+        (winner.clone(), loser)
+    } else {
+        (loser, winner.clone())
+    };
+
+    Some(ProbeCandidate {
+        a,
+        b,
+        expected: winner,
+        kind,
+    })
+}
+
+/// Find preference cycles in a list of comparisons
+///
+/// Each comparison is an edge from winner to loser. Returns one cycle for
+/// every group of items whose answers cannot be put in a consistent order,
+/// e.g. `[a, b, c]` when a beat b, b beat c and c beat a. A pair answered
+/// both ways shows up as a two-item cycle.
+pub fn find_cycles(comparisons: &[Comparison]) -> Vec<Vec<Id>> {
+    let mut edges: BTreeMap<Id, BTreeSet<Id>> = BTreeMap::new();
+    for comparison in comparisons {
+        let loser = if comparison.winner == comparison.a {
+            &comparison.b
+        } else {
+            &comparison.a
+        };
+        edges
+            .entry(comparison.winner.clone())
+            .or_default()
+            .insert(loser.clone());
+        edges.entry(loser.clone()).or_default();
+    }
+
+    strongly_connected(&edges)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .filter_map(|component| shortest_cycle(&edges, &component))
+        .collect()
+}
+
+/// Latest answer for each unordered pair, keyed by `(smaller, larger)` ID
+fn latest_answers(comparisons: &[Comparison]) -> BTreeMap<(Id, Id), Id> {
+    let mut answers = BTreeMap::new();
+    for comparison in comparisons {
+        answers.insert(
+            pair_key(&comparison.a, &comparison.b),
+            comparison.winner.clone(),
+        );
+    }
+    answers
+}
+
+fn winner_graph(answers: &BTreeMap<(Id, Id), Id>) -> BTreeMap<Id, BTreeSet<Id>> {
+    let mut beats: BTreeMap<Id, BTreeSet<Id>> = BTreeMap::new();
+    for ((a, b), winner) in answers {
+        let loser = if winner == a { b } else { a };
+        beats
+            .entry(winner.clone())
+            .or_default()
+            .insert(loser.clone());
+    }
+    beats
+}
+
+fn pair_key(a: &Id, b: &Id) -> (Id, Id) {
+    if a < b {
+        (a.clone(), b.clone())
+    } else {
+        (b.clone(), a.clone())
+    }
+}
+
+/// Tarjan's strongly connected components
+fn strongly_connected(edges: &BTreeMap<Id, BTreeSet<Id>>) -> Vec<Vec<Id>> {
+    struct Tarjan<'a> {
+        edges: &'a BTreeMap<Id, BTreeSet<Id>>,
+        index: HashMap<&'a Id, usize>,
+        lowlink: HashMap<&'a Id, usize>,
+        stack: Vec<&'a Id>,
+        on_stack: BTreeSet<&'a Id>,
+        components: Vec<Vec<Id>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, node: &'a Id) {
+            let idx = self.index.len();
+            self.index.insert(node, idx);
+            self.lowlink.insert(node, idx);
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            for next in &self.edges[node] {
+                if !self.index.contains_key(next) {
+                    self.visit(next);
+                    let low = self.lowlink[node].min(self.lowlink[next]);
+                    self.lowlink.insert(node, low);
+                } else if self.on_stack.contains(next) {
+                    let low = self.lowlink[node].min(self.index[next]);
+                    self.lowlink.insert(node, low);
+                }
+            }
+
+            if self.lowlink[node] == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.clone());
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort();
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        edges,
+        index: HashMap::new(),
+        lowlink: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+
+    for node in edges.keys() {
+        if !tarjan.index.contains_key(node) {
+            tarjan.visit(node);
+        }
+    }
+
+    tarjan.components.sort();
+    tarjan.components
+}
+
+/// Shortest cycle through the first member of a strongly connected component
+fn shortest_cycle(edges: &BTreeMap<Id, BTreeSet<Id>>, component: &[Id]) -> Option<Vec<Id>> {
+    let members: BTreeSet<&Id> = component.iter().collect();
+    let start = component.first()?;
+
+    let mut previous: HashMap<&Id, &Id> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for next in &edges[node] {
+            if !members.contains(next) {
+                continue;
+            }
+
+            if next == start {
+                let mut cycle = vec![node.clone()];
+                let mut current = node;
+                while current != start {
+                    current = previous[current];
+                    cycle.push(current.clone());
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+
+            if !previous.contains_key(next) {
+                previous.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn comparison(a: &str, b: &str, winner: &str) -> Comparison {
+        Comparison {
+            a: Id::from(a),
+            b: Id::from(b),
+            winner: Id::from(winner),
+            timestamp: Utc::now(),
+            shown_first: None,
+            latency_ms: None,
+        }
+    }
+
+    fn ids(values: &[&str]) -> Vec<Id> {
+        values.iter().map(|v| Id::from(*v)).collect()
+    }
+
+    #[test]
+    fn test_no_cycles_in_consistent_answers() {
+        let comparisons = vec![
+            comparison("a", "b", "a"),
+            comparison("b", "c", "b"),
+            comparison("a", "c", "a"),
+        ];
+        assert!(find_cycles(&comparisons).is_empty());
+    }
+
+    #[test]
+    fn test_three_item_cycle() {
+        let comparisons = vec![
+            comparison("a", "b", "a"),
+            comparison("b", "c", "b"),
+            comparison("c", "a", "c"),
+            comparison("d", "a", "a"),
+        ];
+        let cycles = find_cycles(&comparisons);
+        assert_eq!(cycles, vec![ids(&["a", "b", "c"])]);
+    }
+
+    #[test]
+    fn test_contradicting_repeat_is_a_cycle() {
+        let comparisons = vec![comparison("a", "b", "a"), comparison("b", "a", "b")];
+        let cycles = find_cycles(&comparisons);
+        assert_eq!(cycles, vec![ids(&["a", "b"])]);
+    }
+
+    #[test]
+    fn test_separate_cycles() {
+        let comparisons = vec![
+            comparison("a", "b", "a"),
+            comparison("b", "a", "b"),
+            comparison("x", "y", "x"),
+            comparison("y", "z", "y"),
+            comparison("z", "x", "z"),
+        ];
+        assert_eq!(find_cycles(&comparisons).len(), 2);
+    }
+
+    #[test]
+    fn test_select_probe_empty() {
+        let mut rng = StdRng::seed_from_u64(7);
+        assert!(select_probe(&[], &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_select_probe_prefers_transitive() {
+        let comparisons = vec![comparison("a", "b", "a"), comparison("b", "c", "b")];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let probe = select_probe(&comparisons, &mut rng).unwrap();
+        assert_eq!(probe.kind, ProbeKind::Transitive);
+        assert_eq!(probe.expected, Id::from("a"));
+        assert!(probe.a == Id::from("c") || probe.b == Id::from("c"));
+    }
+
+    #[test]
+    fn test_select_probe_repeats_when_nothing_implied() {
+        let comparisons = vec![comparison("a", "b", "b")];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let probe = select_probe(&comparisons, &mut rng).unwrap();
+        assert_eq!(probe.kind, ProbeKind::Repeat);
+        assert_eq!(probe.expected, Id::from("b"));
+    }
+}
@@ -0,0 +1,273 @@
+//! Ordering constraints declared before ranking
+//!
+//! `Above` constraints become pre-answered comparisons for the strategy.
+//! Items with a fixed position are left out of the comparisons entirely and
+//! placed into the final order afterwards.
+
+use crate::{Error, Id, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A known fact about the user's preferences
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Constraint {
+    /// `higher` ranks above `lower`
+    Above { higher: Id, lower: Id },
+    /// The item always ranks first
+    First { item: Id },
+    /// The item always ranks last
+    Last { item: Id },
+    /// The item is at a fixed position, starting from 1
+    Position { item: Id, position: usize },
+}
+
+impl Constraint {
+    /// Items the constraint mentions
+    pub fn items(&self) -> Vec<&Id> {
+        match self {
+            Constraint::Above { higher, lower } => vec![higher, lower],
+            Constraint::First { item }
+            | Constraint::Last { item }
+            | Constraint::Position { item, .. } => vec![item],
+        }
+    }
+}
+
+/// Check that the constraints can all hold for a ranking of `item_count` items
+pub fn validate(constraints: &[Constraint], item_count: usize) -> Result<()> {
+    let fixed = fixed_positions(constraints, item_count)?;
+    let fixed_items: BTreeSet<&Id> = fixed.values().collect();
+
+    for constraint in constraints {
+        if let Constraint::Above { higher, lower } = constraint {
+            if higher == lower {
+                return Err(Error::Other(format!(
+                    "Item {} can't rank above itself",
+                    higher
+                )));
+            }
+            if fixed_items.contains(higher) || fixed_items.contains(lower) {
+                return Err(Error::Other(format!(
+                    "Item {} has a fixed position and can't also be ordered relative to another item",
+                    if fixed_items.contains(higher) {
+                        higher
+                    } else {
+                        lower
+                    }
+                )));
+            }
+        }
+    }
+
+    // Above constraints must not contradict each other
+    let closure = implied_pairs(constraints);
+    if let Some((higher, lower)) = closure
+        .iter()
+        .find(|(h, l)| h != l && closure.contains(&(l.clone(), h.clone())))
+    {
+        return Err(Error::Other(format!(
+            "Constraints contradict each other: {} is both above and below {}",
+            higher, lower
+        )));
+    }
+
+    Ok(())
+}
+
+/// Every (winner, loser) pair implied by the `Above` constraints, including
+/// pairs that follow by transitivity
+pub fn implied_pairs(constraints: &[Constraint]) -> BTreeSet<(Id, Id)> {
+    let mut below: BTreeMap<Id, BTreeSet<Id>> = BTreeMap::new();
+    for constraint in constraints {
+        if let Constraint::Above { higher, lower } = constraint {
+            below
+                .entry(higher.clone())
+                .or_default()
+                .insert(lower.clone());
+        }
+    }
+
+    let mut pairs = BTreeSet::new();
+    for start in below.keys() {
+        // Everything reachable from `start` ranks below it
+        let mut stack: Vec<&Id> = below[start].iter().collect();
+        let mut seen = BTreeSet::new();
+        while let Some(next) = stack.pop() {
+            if !seen.insert(next) {
+                continue;
+            }
+            pairs.insert((start.clone(), next.clone()));
+            if let Some(further) = below.get(next) {
+                stack.extend(further);
+            }
+        }
+    }
+
+    pairs
+}
+
+/// Fixed items keyed by their zero-based position in a ranking of
+/// `item_count` items
+pub fn fixed_positions(
+    constraints: &[Constraint],
+    item_count: usize,
+) -> Result<BTreeMap<usize, Id>> {
+    let mut fixed: BTreeMap<usize, Id> = BTreeMap::new();
+    let mut placed: BTreeSet<&Id> = BTreeSet::new();
+
+    for constraint in constraints {
+        let (item, position) = match constraint {
+            Constraint::Above { .. } => continue,
+            Constraint::First { item } => (item, 1),
+            Constraint::Last { item } => (item, item_count),
+            Constraint::Position { item, position } => (item, *position),
+        };
+
+        if position == 0 || position > item_count {
+            return Err(Error::Other(format!(
+                "Position {} is out of range for {} items",
+                position, item_count
+            )));
+        }
+        if !placed.insert(item) {
+            return Err(Error::Other(format!(
+                "Item {} has more than one fixed position",
+                item
+            )));
+        }
+        if let Some(other) = fixed.insert(position - 1, item.clone()) {
+            return Err(Error::Other(format!(
+                "Items {} and {} are both fixed at position {}",
+                other, item, position
+            )));
+        }
+    }
+
+    Ok(fixed)
+}
+
+/// Items that still need to be compared, i.e. those without a fixed position
+pub fn free_items(items: &[Id], constraints: &[Constraint]) -> Vec<Id> {
+    let fixed: BTreeSet<&Id> = constraints
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::Above { .. } => None,
+            Constraint::First { item }
+            | Constraint::Last { item }
+            | Constraint::Position { item, .. } => Some(item),
+        })
+        .collect();
+
+    items
+        .iter()
+        .filter(|id| !fixed.contains(id))
+        .cloned()
+        .collect()
+}
+
+/// Place the fixed items into an order of the free items
+pub fn apply_fixed(order: &[Id], constraints: &[Constraint]) -> Result<Vec<Id>> {
+    let fixed_count = constraints
+        .iter()
+        .filter(|c| !matches!(c, Constraint::Above { .. }))
+        .count();
+    let fixed = fixed_positions(constraints, order.len() + fixed_count)?;
+
+    let mut result = order.to_vec();
+    for (position, item) in fixed {
+        result.insert(position.min(result.len()), item);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(value: &str) -> Id {
+        Id::from(value)
+    }
+
+    fn above(higher: &str, lower: &str) -> Constraint {
+        Constraint::Above {
+            higher: id(higher),
+            lower: id(lower),
+        }
+    }
+
+    #[test]
+    fn test_implied_pairs_are_transitive() {
+        let pairs = implied_pairs(&[above("a", "b"), above("b", "c")]);
+        let expected: BTreeSet<(Id, Id)> =
+            [(id("a"), id("b")), (id("a"), id("c")), (id("b"), id("c"))]
+                .into_iter()
+                .collect();
+        assert_eq!(pairs, expected);
+    }
+
+    #[test]
+    fn test_validate_rejects_cycle() {
+        let constraints = [above("a", "b"), above("b", "c"), above("c", "a")];
+        assert!(validate(&constraints, 5).is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_conflicting_positions() {
+        let constraints = [
+            Constraint::First { item: id("a") },
+            Constraint::Position {
+                item: id("b"),
+                position: 1,
+            },
+        ];
+        assert!(validate(&constraints, 5).is_err());
+
+        let constraints = [Constraint::Position {
+            item: id("a"),
+            position: 6,
+        }];
+        assert!(validate(&constraints, 5).is_err());
+
+        let constraints = [Constraint::First { item: id("a") }, above("a", "b")];
+        assert!(validate(&constraints, 5).is_err());
+    }
+
+    #[test]
+    fn test_apply_fixed() {
+        let constraints = [
+            Constraint::Last { item: id("z") },
+            Constraint::First { item: id("a") },
+            Constraint::Position {
+                item: id("m"),
+                position: 3,
+            },
+            above("b", "c"),
+        ];
+        let all = [id("a"), id("b"), id("c"), id("d"), id("m"), id("z")];
+        assert!(validate(&constraints, all.len()).is_ok());
+
+        let free = free_items(&all, &constraints);
+        assert_eq!(free, vec![id("b"), id("c"), id("d")]);
+
+        let order = apply_fixed(&[id("d"), id("b"), id("c")], &constraints).unwrap();
+        assert_eq!(
+            order,
+            vec![id("a"), id("d"), id("m"), id("b"), id("c"), id("z")]
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let constraint = Constraint::Position {
+            item: id("a"),
+            position: 2,
+        };
+        let json = serde_json::to_value(&constraint).unwrap();
+        assert_eq!(json["kind"], "position");
+        assert_eq!(json["position"], 2);
+
+        let restored: Constraint = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, constraint);
+    }
+}
@@ -0,0 +1,247 @@
+//! How closely two rankings of the same items agree
+
+use crate::Id;
+use std::collections::{HashMap, HashSet};
+
+/// Kendall's tau between two orders, from -1 (reversed) to 1 (identical)
+///
+/// Only items in both orders are compared. Returns 1 when fewer than two
+/// items are shared, since there is nothing to disagree on.
+pub fn kendall_tau(a: &[Id], b: &[Id]) -> f64 {
+    let position: HashMap<&Id, usize> = b.iter().enumerate().map(|(i, id)| (id, i)).collect();
+    let shared: Vec<usize> = a
+        .iter()
+        .filter_map(|id| position.get(id).copied())
+        .collect();
+
+    let n = shared.len();
+    if n < 2 {
+        return 1.0;
+    }
+
+    let mut concordant = 0i64;
+    let mut discordant = 0i64;
+    for i in 0..n {
+        for j in i + 1..n {
+            if shared[i] < shared[j] {
+                concordant += 1;
+            } else {
+                discordant += 1;
+            }
+        }
+    }
+
+    (concordant - discordant) as f64 / (n * (n - 1) / 2) as f64
+}
+
+/// Spearman's rho between two orders, from -1 (reversed) to 1 (identical)
+///
+/// Only items in both orders are compared, ranked by their position among
+/// the shared items. Returns 1 when fewer than two items are shared.
+pub fn spearman_rho(a: &[Id], b: &[Id]) -> f64 {
+    let in_a: HashSet<&Id> = a.iter().collect();
+    let in_b: HashSet<&Id> = b.iter().collect();
+    let b_rank: HashMap<&Id, usize> = b
+        .iter()
+        .filter(|id| in_a.contains(id))
+        .enumerate()
+        .map(|(i, id)| (id, i))
+        .collect();
+    // The b rank of each shared item, in a's order
+    let ranks: Vec<usize> = a
+        .iter()
+        .filter(|id| in_b.contains(id))
+        .map(|id| b_rank[id])
+        .collect();
+
+    let n = ranks.len();
+    if n < 2 {
+        return 1.0;
+    }
+
+    let squared: f64 = ranks
+        .iter()
+        .enumerate()
+        .map(|(i, &j)| (i as f64 - j as f64).powi(2))
+        .sum();
+    let n = n as f64;
+    1.0 - 6.0 * squared / (n * (n * n - 1.0))
+}
+
+/// Rank-biased overlap between two orders, from 0 (disjoint) to 1
+/// (identical)
+///
+/// Unlike Kendall's tau and Spearman's rho it weighs agreement at the top
+/// most, and items only one order contains count as disagreement. `p` sets
+/// how top-heavy it is: the top `1 / (1 - p)` items carry most of the
+/// weight. This is the extrapolated form for orders of any lengths.
+pub fn rank_biased_overlap(a: &[Id], b: &[Id], p: f64) -> f64 {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let (s, l) = (short.len(), long.len());
+    if s == 0 {
+        return if l == 0 { 1.0 } else { 0.0 };
+    }
+
+    // Overlap of the two orders' top d items, for each depth d
+    let mut seen_short: HashSet<&Id> = HashSet::new();
+    let mut seen_long: HashSet<&Id> = HashSet::new();
+    let mut overlap = 0usize;
+    let mut sum = 0.0;
+    let mut overlap_at_s = 0usize;
+    for d in 1..=l {
+        let from_long = &long[d - 1];
+        if seen_short.contains(from_long) {
+            overlap += 1;
+        }
+        seen_long.insert(from_long);
+        if d <= s {
+            let from_short = &short[d - 1];
+            if seen_long.contains(from_short) {
+                overlap += 1;
+            }
+            seen_short.insert(from_short);
+            if d == s {
+                overlap_at_s = overlap;
+            }
+        }
+        // Past the end of the short order, assume its unseen items keep
+        // turning up at the rate they did within it
+        let extrapolated = if d > s {
+            overlap_at_s as f64 * (d - s) as f64 / (s * d) as f64
+        } else {
+            0.0
+        };
+        sum += (overlap as f64 / d as f64 + extrapolated) * p.powi(d as i32);
+    }
+
+    let tail = (overlap as f64 - overlap_at_s as f64) / l as f64 + overlap_at_s as f64 / s as f64;
+    (1.0 - p) / p * sum + tail * p.powi(l as i32)
+}
+
+/// A pair of items two orders put the other way round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dispute {
+    /// The item the first order puts higher
+    pub first_prefers: Id,
+    /// The item the second order puts higher
+    pub second_prefers: Id,
+    /// How many places apart the items are, added over both orders
+    pub gap: usize,
+}
+
+/// Pairs of shared items the orders disagree on, most disputed first
+///
+/// A pair is more disputed the further apart both orders put its items.
+pub fn disputed_pairs(a: &[Id], b: &[Id]) -> Vec<Dispute> {
+    let position: HashMap<&Id, usize> = b.iter().enumerate().map(|(i, id)| (id, i)).collect();
+    let shared: Vec<(usize, &Id, usize)> = a
+        .iter()
+        .enumerate()
+        .filter_map(|(i, id)| position.get(id).map(|&j| (i, id, j)))
+        .collect();
+
+    let mut disputes = Vec::new();
+    for (k, &(a_high, high, b_high)) in shared.iter().enumerate() {
+        for &(a_low, low, b_low) in &shared[k + 1..] {
+            if b_low < b_high {
+                disputes.push(Dispute {
+                    first_prefers: high.clone(),
+                    second_prefers: low.clone(),
+                    gap: (a_low - a_high) + (b_high - b_low),
+                });
+            }
+        }
+    }
+    disputes.sort_by(|x, y| {
+        y.gap
+            .cmp(&x.gap)
+            .then_with(|| x.first_prefers.cmp(&y.first_prefers))
+            .then_with(|| x.second_prefers.cmp(&y.second_prefers))
+    });
+    disputes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(|n| Id::from(*n)).collect()
+    }
+
+    #[test]
+    fn test_kendall_tau() {
+        let truth = ids(&["a", "b", "c", "d"]);
+        assert_eq!(kendall_tau(&truth, &truth), 1.0);
+        assert_eq!(kendall_tau(&ids(&["d", "c", "b", "a"]), &truth), -1.0);
+
+        // One swapped neighbour: 5 concordant, 1 discordant
+        let swapped = ids(&["a", "c", "b", "d"]);
+        assert!((kendall_tau(&swapped, &truth) - 4.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_spearman_rho() {
+        let truth = ids(&["a", "b", "c", "d"]);
+        assert_eq!(spearman_rho(&truth, &truth), 1.0);
+        assert_eq!(spearman_rho(&ids(&["d", "c", "b", "a"]), &truth), -1.0);
+
+        // One swapped neighbour: squared differences add to 2
+        let swapped = ids(&["a", "c", "b", "d"]);
+        assert!((spearman_rho(&swapped, &truth) - 0.8).abs() < 1e-9);
+
+        // Ranked among shared items only, so x and y change nothing
+        assert_eq!(
+            spearman_rho(&ids(&["a", "x", "b"]), &ids(&["y", "a", "b"])),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_rank_biased_overlap() {
+        let truth = ids(&["a", "b", "c", "d"]);
+        assert!((rank_biased_overlap(&truth, &truth, 0.9) - 1.0).abs() < 1e-9);
+        assert_eq!(rank_biased_overlap(&truth, &ids(&["w", "x"]), 0.9), 0.0);
+
+        // Swapping the top two costs more than swapping the bottom two
+        let top = rank_biased_overlap(&ids(&["b", "a", "c", "d"]), &truth, 0.9);
+        let bottom = rank_biased_overlap(&ids(&["a", "b", "d", "c"]), &truth, 0.9);
+        assert!((top - 0.9).abs() < 1e-9);
+        assert!(bottom > top && bottom < 1.0);
+
+        // A shorter order that agrees as far as it goes counts as agreeing
+        let prefix = ids(&["a", "b"]);
+        assert!((rank_biased_overlap(&prefix, &truth, 0.9) - 1.0).abs() < 1e-9);
+        assert_eq!(
+            rank_biased_overlap(&prefix, &truth, 0.9),
+            rank_biased_overlap(&truth, &prefix, 0.9)
+        );
+    }
+
+    #[test]
+    fn test_disputed_pairs() {
+        let first = ids(&["a", "b", "c", "d"]);
+        let second = ids(&["d", "b", "c", "a"]);
+        let disputes = disputed_pairs(&first, &second);
+
+        // a and d swap ends; a/b, a/c, b/d and c/d are disputed less
+        assert_eq!(disputes.len(), 5);
+        assert_eq!(
+            disputes[0],
+            Dispute {
+                first_prefers: Id::from("a"),
+                second_prefers: Id::from("d"),
+                gap: 6,
+            }
+        );
+        assert!(disputes[1..].iter().all(|d| d.gap == 3));
+        assert!(disputed_pairs(&first, &first).is_empty());
+    }
+
+    #[test]
+    fn test_kendall_tau_shared_items_only() {
+        let truth = ids(&["a", "b", "c"]);
+        assert_eq!(kendall_tau(&ids(&["a", "x", "c"]), &truth), 1.0);
+        assert_eq!(kendall_tau(&ids(&["x"]), &truth), 1.0);
+    }
+}
@@ -0,0 +1,121 @@
+//! Multiple ranking criteria per rankset
+//!
+//! A rankset can ask about items along several criteria, for example taste
+//! and price. Each ranking answers one criterion, and `combine` blends the
+//! per-criterion orders into an overall order.
+
+use crate::{Error, Id, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A question items are ranked on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Criterion {
+    pub name: String,
+    /// Question shown when comparing items, if not the default one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+}
+
+impl Criterion {
+    pub fn new(name: String, question: Option<String>) -> Self {
+        Self { name, question }
+    }
+
+    /// Question to show when comparing items on this criterion
+    pub fn prompt(&self) -> String {
+        self.question
+            .clone()
+            .unwrap_or_else(|| format!("Which is better for {}?", self.name))
+    }
+}
+
+/// Blend several orders into one using per-order weights
+///
+/// Each order gives an item a score from 1.0 (first) to 0.0 (last). An
+/// item's combined score is the weighted average over the orders it appears
+/// in. Returns items with their combined score, best first, ties broken by
+/// ID.
+pub fn combine(orders: &[(&[Id], f64)]) -> Result<Vec<(Id, f64)>> {
+    if orders
+        .iter()
+        .any(|(_, weight)| !weight.is_finite() || *weight < 0.0)
+    {
+        return Err(Error::Other("Weights must not be negative".to_string()));
+    }
+    if orders.iter().map(|(_, weight)| weight).sum::<f64>() <= 0.0 {
+        return Err(Error::Other(
+            "At least one weight must be positive".to_string(),
+        ));
+    }
+
+    let mut totals: HashMap<&Id, (f64, f64)> = HashMap::new();
+    for (order, weight) in orders {
+        let last = order.len().saturating_sub(1).max(1) as f64;
+        for (position, id) in order.iter().enumerate() {
+            let score = 1.0 - position as f64 / last;
+            let entry = totals.entry(id).or_insert((0.0, 0.0));
+            entry.0 += weight * score;
+            entry.1 += weight;
+        }
+    }
+
+    let mut combined: Vec<(Id, f64)> = totals
+        .into_iter()
+        .filter(|(_, (_, weight))| *weight > 0.0)
+        .map(|(id, (sum, weight))| (id.clone(), sum / weight))
+        .collect();
+    combined.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    Ok(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(values: &[&str]) -> Vec<Id> {
+        values.iter().map(|v| Id::from(*v)).collect()
+    }
+
+    #[test]
+    fn test_default_prompt() {
+        let criterion = Criterion::new("price".to_string(), None);
+        assert_eq!(criterion.prompt(), "Which is better for price?");
+
+        let criterion = Criterion::new("price".to_string(), Some("Which is cheaper?".to_string()));
+        assert_eq!(criterion.prompt(), "Which is cheaper?");
+    }
+
+    #[test]
+    fn test_combine_follows_weights() {
+        let taste = ids(&["a", "b", "c"]);
+        let price = ids(&["c", "b", "a"]);
+
+        let combined = combine(&[(&taste, 3.0), (&price, 1.0)]).unwrap();
+        let order: Vec<Id> = combined.iter().map(|(id, _)| id.clone()).collect();
+        assert_eq!(order, ids(&["a", "b", "c"]));
+        assert!((combined[0].1 - 0.75).abs() < 1e-9);
+
+        let combined = combine(&[(&taste, 1.0), (&price, 3.0)]).unwrap();
+        assert_eq!(combined[0].0, Id::from("c"));
+    }
+
+    #[test]
+    fn test_combine_equal_weights_tie() {
+        let taste = ids(&["a", "b"]);
+        let price = ids(&["b", "a"]);
+
+        let combined = combine(&[(&taste, 1.0), (&price, 1.0)]).unwrap();
+        assert_eq!(combined[0].1, combined[1].1);
+        assert_eq!(combined[0].0, Id::from("a"));
+    }
+
+    #[test]
+    fn test_combine_rejects_bad_weights() {
+        let taste = ids(&["a", "b"]);
+        assert!(combine(&[(&taste, -1.0)]).is_err());
+        assert!(combine(&[(&taste, 0.0)]).is_err());
+        assert!(combine(&[]).is_err());
+    }
+}
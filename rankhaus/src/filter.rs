@@ -0,0 +1,177 @@
+//! Ranking a subset of items
+//!
+//! A filter picks items by tag, by attribute or by explicit selection. A
+//! ranking made with a filter only covers the matching items and is kept
+//! apart from rankings of other subsets.
+
+use crate::{Id, Item};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Which items a ranking covers
+///
+/// An item matches when it has every tag, every attribute matches and, if
+/// items are selected explicitly, it is one of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemFilter {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Field or attribute name to an exact value (ignoring case) or an
+    /// inclusive numeric range such as "1990..1999"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<Id>,
+}
+
+impl ItemFilter {
+    /// Create a filter, normalizing it so equal filters compare equal
+    pub fn new(tags: Vec<String>, attributes: BTreeMap<String, String>, items: Vec<Id>) -> Self {
+        let mut tags: Vec<String> = tags.into_iter().map(|t| t.to_lowercase()).collect();
+        tags.sort();
+        tags.dedup();
+        let mut items = items;
+        items.sort();
+        items.dedup();
+        Self {
+            tags,
+            attributes,
+            items,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.attributes.is_empty() && self.items.is_empty()
+    }
+
+    pub fn matches(&self, item: &Item) -> bool {
+        self.tags.iter().all(|tag| item.has_tag(tag))
+            && self.attributes.iter().all(|(key, pattern)| {
+                item.field(key)
+                    .is_some_and(|value| value_matches(&value, pattern))
+            })
+            && (self.items.is_empty() || self.items.contains(&item.id))
+    }
+
+    /// IDs of the matching items, sorted
+    pub fn apply<'a>(&self, items: impl IntoIterator<Item = &'a Item>) -> Vec<Id> {
+        let mut ids: Vec<Id> = items
+            .into_iter()
+            .filter(|item| self.matches(item))
+            .map(|item| item.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Short description, e.g. "#90s, year=1990..1999"
+    pub fn describe(&self) -> String {
+        let mut parts: Vec<String> = self.tags.iter().map(|t| format!("#{}", t)).collect();
+        parts.extend(self.attributes.iter().map(|(k, v)| format!("{}={}", k, v)));
+        match self.items.len() {
+            0 => {}
+            1 => parts.push("1 selected item".to_string()),
+            n => parts.push(format!("{} selected items", n)),
+        }
+        parts.join(", ")
+    }
+}
+
+/// Match a value against an exact value or a "LOW..HIGH" range
+///
+/// Either end of a range may be left out. A range whose bounds aren't
+/// numbers is compared as plain text.
+fn value_matches(value: &str, pattern: &str) -> bool {
+    if let Some((low, high)) = pattern.split_once("..") {
+        let bound = |b: &str| -> Option<Option<f64>> {
+            let b = b.trim();
+            if b.is_empty() {
+                Some(None)
+            } else {
+                b.parse().ok().map(Some)
+            }
+        };
+        if let (Some(low), Some(high), Ok(value)) = (bound(low), bound(high), value.trim().parse())
+        {
+            let value: f64 = value;
+            return low.is_none_or(|low| value >= low) && high.is_none_or(|high| value <= high);
+        }
+    }
+    value.trim().eq_ignore_ascii_case(pattern.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn movie(id: &str, year: &str, tags: &[&str]) -> Item {
+        let mut item = Item::with_id(Id::from(id), id.to_string(), chrono::Utc::now());
+        item.attributes.insert("year".to_string(), year.to_string());
+        for tag in tags {
+            item.add_tag(tag);
+        }
+        item
+    }
+
+    fn attributes(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_attribute_range() {
+        let movies = [
+            movie("a", "1988", &[]),
+            movie("b", "1994", &[]),
+            movie("c", "1999", &[]),
+            movie("d", "2001", &[]),
+        ];
+
+        let nineties = ItemFilter::new(vec![], attributes(&[("year", "1990..1999")]), vec![]);
+        assert_eq!(nineties.apply(&movies), vec![Id::from("b"), Id::from("c")]);
+
+        let since = ItemFilter::new(vec![], attributes(&[("year", "1999..")]), vec![]);
+        assert_eq!(since.apply(&movies), vec![Id::from("c"), Id::from("d")]);
+
+        let exact = ItemFilter::new(vec![], attributes(&[("year", "1988")]), vec![]);
+        assert_eq!(exact.apply(&movies), vec![Id::from("a")]);
+    }
+
+    #[test]
+    fn test_tags_and_selection() {
+        let movies = [
+            movie("a", "1988", &["Drama"]),
+            movie("b", "1994", &["drama", "crime"]),
+            movie("c", "1999", &["crime"]),
+        ];
+
+        let filter = ItemFilter::new(vec!["DRAMA".to_string()], BTreeMap::new(), vec![]);
+        assert_eq!(filter.apply(&movies), vec![Id::from("a"), Id::from("b")]);
+
+        let filter = ItemFilter::new(
+            vec!["crime".to_string()],
+            BTreeMap::new(),
+            vec![Id::from("c"), Id::from("a")],
+        );
+        assert_eq!(filter.apply(&movies), vec![Id::from("c")]);
+    }
+
+    #[test]
+    fn test_normalized_equality() {
+        let a = ItemFilter::new(
+            vec!["b".to_string(), "A".to_string()],
+            BTreeMap::new(),
+            vec![Id::from("y"), Id::from("x")],
+        );
+        let b = ItemFilter::new(
+            vec!["a".to_string(), "b".to_string(), "b".to_string()],
+            BTreeMap::new(),
+            vec![Id::from("x"), Id::from("y")],
+        );
+        assert_eq!(a, b);
+        assert_eq!(a.describe(), "#a, #b, 2 selected items");
+        assert!(ItemFilter::default().is_empty());
+    }
+}
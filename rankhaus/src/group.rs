@@ -0,0 +1,182 @@
+//! Hierarchical ranking by item groups
+//!
+//! Items can belong to a named group, such as a division. Each group is
+//! ranked on its own first, and the group orders are then combined either by
+//! merging them or by ranking one representative per group.
+
+use crate::{Id, Item};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Group name used for items without a group
+pub const UNGROUPED: &str = "Ungrouped";
+
+/// How group orders are combined into the final ranking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupMode {
+    /// Merge the group orders into one interleaved order
+    Merge,
+    /// Rank each group's best item, then list whole groups in that order
+    Representatives,
+}
+
+/// A group and its items
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemGroup {
+    pub name: String,
+    /// Item IDs, best to worst once ranked
+    pub items: Vec<Id>,
+}
+
+/// Split items into groups by name, ungrouped items last
+///
+/// Items within a group are in ID order.
+pub fn partition<'a>(items: impl IntoIterator<Item = &'a Item>) -> Vec<ItemGroup> {
+    let mut named: BTreeMap<&str, Vec<Id>> = BTreeMap::new();
+    let mut ungrouped = Vec::new();
+
+    for item in items {
+        match &item.group {
+            Some(group) => named.entry(group).or_default().push(item.id.clone()),
+            None => ungrouped.push(item.id.clone()),
+        }
+    }
+
+    let mut groups: Vec<ItemGroup> = named
+        .into_iter()
+        .map(|(name, items)| ItemGroup {
+            name: name.to_string(),
+            items,
+        })
+        .collect();
+    if !ungrouped.is_empty() {
+        groups.push(ItemGroup {
+            name: UNGROUPED.to_string(),
+            items: ungrouped,
+        });
+    }
+
+    for group in &mut groups {
+        group.items.sort();
+    }
+    groups
+}
+
+impl ItemGroup {
+    /// Every (winner, loser) pair decided by the group's ranked order
+    pub fn implied_pairs(&self) -> Vec<(Id, Id)> {
+        self.items
+            .iter()
+            .enumerate()
+            .flat_map(|(i, winner)| {
+                self.items[i + 1..]
+                    .iter()
+                    .map(move |loser| (winner.clone(), loser.clone()))
+            })
+            .collect()
+    }
+}
+
+/// Reorder groups and their items to follow `order`
+///
+/// Each group's items are sorted by their position in `order`, and groups
+/// are sorted by the position of their best item. Items missing from
+/// `order` go last.
+pub fn arrange(groups: &[ItemGroup], order: &[Id]) -> Vec<ItemGroup> {
+    let position: HashMap<&Id, usize> = order.iter().enumerate().map(|(i, id)| (id, i)).collect();
+    let rank = |id: &Id| position.get(id).copied().unwrap_or(usize::MAX);
+
+    let mut arranged: Vec<ItemGroup> = groups
+        .iter()
+        .map(|group| {
+            let mut items = group.items.clone();
+            items.sort_by_key(|id| rank(id));
+            ItemGroup {
+                name: group.name.clone(),
+                items,
+            }
+        })
+        .collect();
+
+    arranged.sort_by_key(|group| group.items.first().map(rank).unwrap_or(usize::MAX));
+    arranged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn item(id: &str, group: Option<&str>) -> Item {
+        let mut item = Item::with_id(Id::from(id), id.to_string(), Utc::now());
+        item.group = group.map(str::to_string);
+        item
+    }
+
+    fn ids(values: &[&str]) -> Vec<Id> {
+        values.iter().map(|v| Id::from(*v)).collect()
+    }
+
+    #[test]
+    fn test_partition() {
+        let items = [
+            item("d", Some("west")),
+            item("a", Some("east")),
+            item("c", None),
+            item("b", Some("west")),
+        ];
+        let groups = partition(&items);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].name, "east");
+        assert_eq!(groups[0].items, ids(&["a"]));
+        assert_eq!(groups[1].name, "west");
+        assert_eq!(groups[1].items, ids(&["b", "d"]));
+        assert_eq!(groups[2].name, UNGROUPED);
+        assert_eq!(groups[2].items, ids(&["c"]));
+    }
+
+    #[test]
+    fn test_arrange_follows_order() {
+        let groups = vec![
+            ItemGroup {
+                name: "east".to_string(),
+                items: ids(&["a", "b"]),
+            },
+            ItemGroup {
+                name: "west".to_string(),
+                items: ids(&["c", "d"]),
+            },
+        ];
+
+        let arranged = arrange(&groups, &ids(&["d", "a", "c", "b"]));
+        assert_eq!(arranged[0].name, "west");
+        assert_eq!(arranged[0].items, ids(&["d", "c"]));
+        assert_eq!(arranged[1].name, "east");
+        assert_eq!(arranged[1].items, ids(&["a", "b"]));
+    }
+
+    #[test]
+    fn test_implied_pairs() {
+        let group = ItemGroup {
+            name: "east".to_string(),
+            items: ids(&["b", "a", "c"]),
+        };
+        let pairs = group.implied_pairs();
+        assert_eq!(
+            pairs,
+            vec![
+                (Id::from("b"), Id::from("a")),
+                (Id::from("b"), Id::from("c")),
+                (Id::from("a"), Id::from("c")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mode_serialization() {
+        let json = serde_json::to_string(&GroupMode::Representatives).unwrap();
+        assert_eq!(json, "\"representatives\"");
+    }
+}
@@ -0,0 +1,215 @@
+//! How a user's rankings change over time
+//!
+//! Each completed ranking of the same question is a snapshot. Comparing
+//! consecutive snapshots shows which items rose or fell and how settled the
+//! user's view is.
+
+use crate::{correlation, Id, RankSet};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// One completed ranking in a user's history
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Snapshot {
+    pub session_id: Id,
+    pub completed: DateTime<Utc>,
+    pub strategy: String,
+    /// Items best first
+    pub order: Vec<Id>,
+    /// Kendall's tau against the previous snapshot
+    pub kendall_tau_to_previous: Option<f64>,
+}
+
+/// How one item moved across the snapshots
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Drift {
+    pub item: Id,
+    /// 1-based position in each snapshot, `None` where it wasn't ranked
+    pub positions: Vec<Option<usize>>,
+    /// Places gained from its first snapshot to its latest; negative for a
+    /// fall
+    pub change: i64,
+    /// From 0 (moved as far as possible at every step) to 1 (never moved);
+    /// `None` if it was ranked fewer than twice
+    pub stability: Option<f64>,
+}
+
+/// A user's snapshots, oldest first, and how each item drifted
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct History {
+    pub snapshots: Vec<Snapshot>,
+    /// Items by their latest position; items missing from the latest
+    /// snapshot come last
+    pub drift: Vec<Drift>,
+}
+
+impl History {
+    /// History of `user_id`'s completed rankings of all items on
+    /// `criterion` (`None` for the default question)
+    pub fn for_user(rankset: &RankSet, user_id: &Id, criterion: Option<&str>) -> Self {
+        let snapshots = rankset
+            .rankings
+            .iter()
+            .filter(|r| &r.user_id == user_id && r.in_scope(criterion, None))
+            .filter_map(|r| {
+                Some(Snapshot {
+                    session_id: r.session.info.id.clone(),
+                    completed: r.session.info.completed?,
+                    strategy: r.strategy.clone(),
+                    order: r.result.as_ref()?.order.clone()?,
+                    kendall_tau_to_previous: None,
+                })
+            })
+            .collect();
+        Self::from_snapshots(snapshots)
+    }
+
+    /// Work out the drift between snapshots, in any order
+    pub fn from_snapshots(mut snapshots: Vec<Snapshot>) -> Self {
+        snapshots.sort_by_key(|s| s.completed);
+        for i in 1..snapshots.len() {
+            let tau = correlation::kendall_tau(&snapshots[i].order, &snapshots[i - 1].order);
+            snapshots[i].kendall_tau_to_previous = Some(tau);
+        }
+
+        let positions: Vec<HashMap<&Id, usize>> = snapshots
+            .iter()
+            .map(|s| {
+                s.order
+                    .iter()
+                    .enumerate()
+                    .map(|(i, id)| (id, i + 1))
+                    .collect()
+            })
+            .collect();
+        let largest = snapshots.iter().map(|s| s.order.len()).max().unwrap_or(0);
+
+        let mut items: Vec<&Id> = positions.iter().flat_map(|p| p.keys().copied()).collect();
+        items.sort();
+        items.dedup();
+
+        let mut drift: Vec<Drift> = items
+            .into_iter()
+            .map(|id| {
+                let path: Vec<Option<usize>> =
+                    positions.iter().map(|p| p.get(id).copied()).collect();
+                let ranked: Vec<usize> = path.iter().flatten().copied().collect();
+                let change = match (ranked.first(), ranked.last()) {
+                    (Some(&first), Some(&last)) => first as i64 - last as i64,
+                    _ => 0,
+                };
+                let stability = (ranked.len() >= 2 && largest >= 2).then(|| {
+                    let steps: usize = ranked.windows(2).map(|w| w[0].abs_diff(w[1])).sum();
+                    let mean_step = steps as f64 / (ranked.len() - 1) as f64;
+                    1.0 - mean_step / (largest - 1) as f64
+                });
+                Drift {
+                    item: id.clone(),
+                    positions: path,
+                    change,
+                    stability,
+                }
+            })
+            .collect();
+
+        drift.sort_by_key(|d| d.positions.last().copied().flatten().unwrap_or(usize::MAX));
+        Self { snapshots, drift }
+    }
+
+    /// Items that rose, furthest first
+    pub fn risers(&self) -> Vec<&Drift> {
+        let mut risers: Vec<&Drift> = self.drift.iter().filter(|d| d.change > 0).collect();
+        risers.sort_by_key(|d| -d.change);
+        risers
+    }
+
+    /// Items that fell, furthest first
+    pub fn fallers(&self) -> Vec<&Drift> {
+        let mut fallers: Vec<&Drift> = self.drift.iter().filter(|d| d.change < 0).collect();
+        fallers.sort_by_key(|d| d.change);
+        fallers
+    }
+
+    /// Mean Kendall's tau between consecutive snapshots, if there are at
+    /// least two
+    pub fn stability(&self) -> Option<f64> {
+        let taus: Vec<f64> = self
+            .snapshots
+            .iter()
+            .filter_map(|s| s.kendall_tau_to_previous)
+            .collect();
+        (!taus.is_empty()).then(|| taus.iter().sum::<f64>() / taus.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn snapshot(day: u32, order: &[&str]) -> Snapshot {
+        Snapshot {
+            session_id: Id::from(format!("s{}", day)),
+            completed: Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap(),
+            strategy: "merge".to_string(),
+            order: order.iter().map(|n| Id::from(*n)).collect(),
+            kendall_tau_to_previous: None,
+        }
+    }
+
+    fn ids(drift: &[&Drift]) -> Vec<String> {
+        drift.iter().map(|d| d.item.to_string()).collect()
+    }
+
+    #[test]
+    fn test_drift() {
+        // Given out of order; sorted by completion
+        let history = History::from_snapshots(vec![
+            snapshot(20, &["c", "a", "b", "d"]),
+            snapshot(1, &["a", "b", "c", "d"]),
+            snapshot(10, &["a", "c", "b", "d"]),
+        ]);
+
+        assert_eq!(history.snapshots[0].session_id.as_str(), "s1");
+        assert_eq!(history.snapshots[0].kendall_tau_to_previous, None);
+        let tau = history.snapshots[1].kendall_tau_to_previous.unwrap();
+        assert!((tau - 4.0 / 6.0).abs() < 1e-9);
+
+        // Listed by latest position
+        let order: Vec<&str> = history.drift.iter().map(|d| d.item.as_str()).collect();
+        assert_eq!(order, vec!["c", "a", "b", "d"]);
+
+        let c = &history.drift[0];
+        assert_eq!(c.positions, vec![Some(3), Some(2), Some(1)]);
+        assert_eq!(c.change, 2);
+        // One place per step, out of at most three
+        assert!((c.stability.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(history.drift[3].stability, Some(1.0));
+
+        assert_eq!(ids(&history.risers()), vec!["c"]);
+        assert_eq!(ids(&history.fallers()), vec!["a", "b"]);
+        assert!(history.stability().unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_items_added_later() {
+        let history = History::from_snapshots(vec![
+            snapshot(1, &["a", "b"]),
+            snapshot(2, &["c", "b", "a"]),
+        ]);
+
+        let c = history
+            .drift
+            .iter()
+            .find(|d| d.item.as_str() == "c")
+            .unwrap();
+        assert_eq!(c.positions, vec![None, Some(1)]);
+        assert_eq!(c.change, 0);
+        assert_eq!(c.stability, None);
+
+        let single = History::from_snapshots(vec![snapshot(1, &["a", "b"])]);
+        assert_eq!(single.stability(), None);
+        assert!(single.risers().is_empty());
+    }
+}
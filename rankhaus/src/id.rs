@@ -3,15 +3,23 @@ use std::fmt;
 use std::str::FromStr;
 
 /// A 7-character alphanumeric identifier
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Default)]
 pub struct Id(String);
 
 impl Id {
     /// Generate a new random ID with optional prefix
+    ///
+    /// Drawn from the thread's random generator, not a ranking's seed, so
+    /// item and user IDs differ between otherwise identical runs. Use
+    /// `generate` for IDs that can be reproduced.
     pub fn new(prefix: Option<&str>) -> Self {
-        use rand::Rng;
+        Self::generate(prefix, &mut rand::rng())
+    }
+
+    /// Generate an ID from the given random source, so IDs can be
+    /// reproduced from a seed
+    pub fn generate<R: rand::Rng + ?Sized>(prefix: Option<&str>, rng: &mut R) -> Self {
         const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
-        let mut rng = rand::rng();
 
         let random: String = (0..7)
             .map(|_| {
@@ -80,4 +88,16 @@ mod tests {
         assert_eq!(id.as_str().len(), 8);
         assert!(id.as_str().starts_with("u"));
     }
+
+    #[test]
+    fn test_seeded_generation() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let a = Id::generate(Some("s"), &mut StdRng::seed_from_u64(1));
+        let b = Id::generate(Some("s"), &mut StdRng::seed_from_u64(1));
+        let c = Id::generate(Some("s"), &mut StdRng::seed_from_u64(2));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
 }
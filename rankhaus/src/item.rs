@@ -1,6 +1,7 @@
 use crate::Id;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// An item to be ranked
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,20 @@ pub struct Item {
     pub id: Id,
     pub value: String,
     pub created: DateTime<Utc>,
+    /// Optional group, e.g. a division, for ranking by group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Longer description shown for context while ranking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Link to more information
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Free-form metadata, e.g. "year" = "2015"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
 }
 
 impl Item {
@@ -18,12 +33,72 @@ impl Item {
             id: Id::new(None),
             value,
             created: Utc::now(),
+            group: None,
+            description: None,
+            url: None,
+            tags: Vec::new(),
+            attributes: BTreeMap::new(),
         }
     }
 
     /// Create an item with a specific ID (for deserialization)
     pub fn with_id(id: Id, value: String, created: DateTime<Utc>) -> Self {
-        Self { id, value, created }
+        Self {
+            id,
+            value,
+            created,
+            group: None,
+            description: None,
+            url: None,
+            tags: Vec::new(),
+            attributes: BTreeMap::new(),
+        }
+    }
+
+    /// Whether the item has anything beyond its value and group
+    pub fn has_details(&self) -> bool {
+        self.description.is_some()
+            || self.url.is_some()
+            || !self.tags.is_empty()
+            || !self.attributes.is_empty()
+    }
+
+    /// Add a tag, ignoring case when checking for duplicates
+    ///
+    /// Returns `false` if the item already had it.
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        if self.has_tag(tag) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    /// Remove a tag, ignoring case. Returns `false` if the item didn't have it.
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+        self.tags.len() != before
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Look up a field by name for display templates
+    ///
+    /// Knows `value`, `id`, `group`, `description`, `url` and `tags` (comma
+    /// separated); any other name is looked up in the attributes.
+    pub fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "value" => Some(self.value.clone()),
+            "id" => Some(self.id.to_string()),
+            "group" => self.group.clone(),
+            "description" => self.description.clone(),
+            "url" => self.url.clone(),
+            "tags" => (!self.tags.is_empty()).then(|| self.tags.join(", ")),
+            key => self.attributes.get(key).cloned(),
+        }
     }
 }
 
@@ -37,4 +112,37 @@ mod tests {
         assert_eq!(item.value, "test");
         assert_eq!(item.id.as_str().len(), 7);
     }
+
+    #[test]
+    fn test_tags_and_fields() {
+        let mut item = Item::new("Rust".to_string());
+        assert!(!item.has_details());
+
+        assert!(item.add_tag("systems"));
+        assert!(!item.add_tag("Systems"));
+        item.add_tag("compiled");
+        item.attributes
+            .insert("year".to_string(), "2015".to_string());
+        assert!(item.has_details());
+
+        assert_eq!(item.field("tags").as_deref(), Some("systems, compiled"));
+        assert_eq!(item.field("year").as_deref(), Some("2015"));
+        assert_eq!(item.field("url"), None);
+
+        assert!(item.remove_tag("SYSTEMS"));
+        assert!(!item.remove_tag("systems"));
+        assert_eq!(item.tags, vec!["compiled".to_string()]);
+    }
+
+    #[test]
+    fn test_old_items_still_load() {
+        let json = r#"{"value": "blue", "created": "2025-10-31T17:15:00Z"}"#;
+        let item: Item = serde_json::from_str(json).unwrap();
+        assert!(item.tags.is_empty());
+        assert!(!item.has_details());
+
+        let json = serde_json::to_string(&item).unwrap();
+        assert!(!json.contains("tags"));
+        assert!(!json.contains("attributes"));
+    }
 }
@@ -0,0 +1,229 @@
+//! How each item fares across users' rankings and answers
+//!
+//! Rank figures come from every user's latest completed ranking of all
+//! items. Win rates come from recorded comparisons, including those of
+//! sessions still in progress.
+
+use crate::session::Comparison;
+use crate::{Id, RankSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// An item's wins and losses in direct comparisons
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WinLoss {
+    pub wins: usize,
+    pub losses: usize,
+}
+
+impl WinLoss {
+    /// Tally each item's wins and losses in `comparisons`
+    pub fn from_comparisons<'a>(
+        comparisons: impl IntoIterator<Item = &'a Comparison>,
+    ) -> BTreeMap<Id, WinLoss> {
+        let mut records: BTreeMap<Id, WinLoss> = BTreeMap::new();
+        for comparison in comparisons {
+            let loser = if comparison.winner == comparison.a {
+                &comparison.b
+            } else {
+                &comparison.a
+            };
+            records.entry(comparison.winner.clone()).or_default().wins += 1;
+            records.entry(loser.clone()).or_default().losses += 1;
+        }
+        records
+    }
+
+    pub fn total(&self) -> usize {
+        self.wins + self.losses
+    }
+
+    /// Fraction of comparisons won, if the item was compared at all
+    pub fn win_rate(&self) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some(self.wins as f64 / total as f64),
+        }
+    }
+}
+
+/// Where users ranked an item, 1 being best
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankSpread {
+    /// Number of users whose ranking includes the item
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    pub best: usize,
+    pub worst: usize,
+}
+
+/// Statistics for one item
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ItemStats {
+    pub item: Id,
+    /// `None` if no user has ranked the item
+    pub rank: Option<RankSpread>,
+    pub record: WinLoss,
+    /// How split users are on the item, from 0 (every user puts it in the
+    /// same place) to 1 (half put it first and half last)
+    ///
+    /// This is the rank's standard deviation over the largest possible,
+    /// `(n - 1) / 2` for `n` items. `None` with fewer than two rankings.
+    pub controversy: Option<f64>,
+}
+
+/// Statistics for every item in `rankset` on `criterion` (`None` for the
+/// default question), best mean rank first
+///
+/// Items nobody has ranked come last, by ID.
+pub fn item_stats(rankset: &RankSet, criterion: Option<&str>) -> Vec<ItemStats> {
+    let mut ranks: HashMap<&Id, Vec<usize>> = HashMap::new();
+    let mut largest = 0;
+    for user in rankset.users.values() {
+        let order = rankset
+            .latest_ranking(&user.id, criterion, None)
+            .and_then(|r| r.result.as_ref())
+            .and_then(|r| r.order.as_ref());
+        if let Some(order) = order {
+            largest = largest.max(order.len());
+            for (position, id) in order.iter().enumerate() {
+                ranks.entry(id).or_default().push(position + 1);
+            }
+        }
+    }
+
+    let mut records: BTreeMap<Id, WinLoss> = BTreeMap::new();
+    for ranking in rankset
+        .rankings
+        .iter()
+        .filter(|r| r.criterion.as_deref() == criterion)
+    {
+        for (id, record) in ranking.session.item_records() {
+            let total = records.entry(id).or_default();
+            total.wins += record.wins;
+            total.losses += record.losses;
+        }
+    }
+
+    let mut stats: Vec<ItemStats> = rankset
+        .items
+        .values()
+        .map(|item| {
+            let rank = ranks.get(&item.id).map(|r| spread(r));
+            let controversy = match &rank {
+                Some(rank) if rank.count >= 2 && largest >= 2 => {
+                    Some((rank.std_dev / ((largest - 1) as f64 / 2.0)).min(1.0))
+                }
+                _ => None,
+            };
+            ItemStats {
+                item: item.id.clone(),
+                rank,
+                record: records.get(&item.id).copied().unwrap_or_default(),
+                controversy,
+            }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| {
+        let mean = |s: &ItemStats| s.rank.as_ref().map_or(f64::INFINITY, |r| r.mean);
+        mean(a)
+            .total_cmp(&mean(b))
+            .then_with(|| a.item.cmp(&b.item))
+    });
+    stats
+}
+
+fn spread(ranks: &[usize]) -> RankSpread {
+    let mut sorted = ranks.to_vec();
+    sorted.sort();
+    let n = sorted.len();
+    let mean = sorted.iter().sum::<usize>() as f64 / n as f64;
+    let median = if n % 2 == 1 {
+        sorted[n / 2] as f64
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) as f64 / 2.0
+    };
+    let variance = sorted
+        .iter()
+        .map(|&r| (r as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n as f64;
+
+    RankSpread {
+        count: n,
+        mean,
+        median,
+        std_dev: variance.sqrt(),
+        best: sorted[0],
+        worst: sorted[n - 1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ranking::completed_ranking;
+    use crate::{Item, User};
+
+    fn rankset_with(orders: &[&[&str]]) -> RankSet {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        for name in ["a", "b", "c", "d"] {
+            let item = Item::with_id(Id::from(name), name.to_string(), chrono::Utc::now());
+            rankset.add_item(item).unwrap();
+        }
+        for (i, order) in orders.iter().enumerate() {
+            let user = User::new(format!("user{}", i), None);
+            let order: Vec<Id> = order.iter().map(|n| Id::from(*n)).collect();
+            let ranking = completed_ranking(&user.id, &order);
+            rankset.add_user(user).unwrap();
+            rankset.rankings.push(ranking);
+        }
+        rankset
+    }
+
+    fn find<'a>(stats: &'a [ItemStats], name: &str) -> &'a ItemStats {
+        stats.iter().find(|s| s.item.as_str() == name).unwrap()
+    }
+
+    #[test]
+    fn test_rank_spread_and_controversy() {
+        let rankset = rankset_with(&[&["a", "b", "c", "d"], &["d", "b", "c", "a"]]);
+        let stats = item_stats(&rankset, None);
+
+        // b is second for both; a and d are first for one and last for the other
+        let b = find(&stats, "b").rank.clone().unwrap();
+        assert_eq!((b.mean, b.median, b.std_dev), (2.0, 2.0, 0.0));
+        assert_eq!(find(&stats, "b").controversy, Some(0.0));
+
+        let a = find(&stats, "a");
+        let rank = a.rank.as_ref().unwrap();
+        assert_eq!((rank.best, rank.worst, rank.count), (1, 4, 2));
+        assert_eq!(rank.mean, 2.5);
+        assert_eq!(a.controversy, Some(1.0));
+        assert_eq!(stats[0].item.as_str(), "b");
+    }
+
+    #[test]
+    fn test_win_rate_from_comparisons() {
+        let mut rankset = rankset_with(&[&["a", "b", "c", "d"]]);
+        let session = &mut rankset.rankings[0].session;
+        session.add_comparison(Id::from("a"), Id::from("b"), Id::from("a"));
+        session.add_comparison(Id::from("a"), Id::from("c"), Id::from("c"));
+        session.add_comparison(Id::from("b"), Id::from("c"), Id::from("b"));
+        // Cleared comparisons still count
+        session.clear_comparisons();
+
+        let stats = item_stats(&rankset, None);
+        let a = find(&stats, "a");
+        assert_eq!(a.record, WinLoss { wins: 1, losses: 1 });
+        assert_eq!(a.record.win_rate(), Some(0.5));
+        assert_eq!(find(&stats, "d").record.win_rate(), None);
+
+        // A single ranking can't be controversial
+        assert_eq!(a.controversy, None);
+    }
+}
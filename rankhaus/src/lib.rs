@@ -3,13 +3,34 @@
 //! This library provides core data structures and ranking strategies for
 //! performing pairwise comparisons and generating ranked orderings.
 
+pub mod aggregate;
+pub mod agreement;
+pub mod budget;
+pub mod consistency;
+pub mod constraint;
+pub mod correlation;
+pub mod criteria;
 pub mod error;
+pub mod filter;
+pub mod group;
+pub mod history;
 pub mod id;
 pub mod item;
+pub mod item_stats;
+pub mod pooled;
+pub mod presentation;
+pub mod prompt;
 pub mod ranking;
 pub mod rankset;
+pub mod score;
+pub mod seed;
 pub mod session;
+pub mod simulate;
 pub mod strategy;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
+pub mod tier;
+pub mod timing;
 pub mod user;
 
 // Re-export commonly used types
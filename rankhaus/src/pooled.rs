@@ -0,0 +1,357 @@
+//! Global ratings fitted from every user's answers
+//!
+//! Instead of combining users' final orders, every recorded comparison is
+//! pooled into one Bradley–Terry model, in which item `i` beats item `j`
+//! with probability `p_i / (p_i + p_j)`. Strengths are fitted with Hunter's
+//! MM algorithm and reported on the Elo scale, with standard errors from the
+//! curvature of the likelihood.
+//!
+//! Answers a ranking counts as weak preferences, such as slow ones under
+//! `rank --weak-after`, count as part of a game.
+//!
+//! Each item also plays one imaginary win and one imaginary loss against an
+//! average item rated 1500. This keeps items that never lost (or never won)
+//! at a finite rating, and pulls items with few answers towards the middle.
+
+use crate::item_stats::WinLoss;
+use crate::session::Comparison;
+use crate::{Id, RankSet};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::f64::consts::LN_10;
+
+/// Rating of the imaginary average item
+pub const RATING_CENTER: f64 = 1500.0;
+
+/// Elo points per unit of log strength
+const ELO_PER_LOG: f64 = 400.0 / LN_10;
+
+/// Imaginary wins, and as many losses, each item has against an average item
+const PRIOR_GAMES: f64 = 1.0;
+
+const MAX_ITERATIONS: usize = 10_000;
+
+/// Largest change in log strength at which the fit counts as converged
+const TOLERANCE: f64 = 1e-12;
+
+/// One item's pooled rating
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PooledRating {
+    pub item: Id,
+    /// Bradley–Terry strength on the Elo scale: 400 points apart means 10:1
+    /// odds
+    pub rating: f64,
+    /// Standard error of the rating, in the same points
+    pub std_error: f64,
+    pub record: WinLoss,
+}
+
+impl PooledRating {
+    /// Rating range `z` standard errors either side, e.g. 1.96 for 95%
+    pub fn interval(&self, z: f64) -> (f64, f64) {
+        (
+            self.rating - z * self.std_error,
+            self.rating + z * self.std_error,
+        )
+    }
+}
+
+/// Pooled ratings for every item, and where the answers came from
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Leaderboard {
+    /// Highest rating first
+    pub ratings: Vec<PooledRating>,
+    /// Comparisons the ratings were fitted from, weak ones included
+    pub comparisons: usize,
+    /// Users who answered at least one of them
+    pub users: usize,
+    /// Sessions that contributed comparisons
+    pub sessions: usize,
+    /// Finished sessions whose comparisons were cleared, so count for nothing
+    pub cleared_sessions: usize,
+}
+
+/// Fit pooled ratings from every comparison recorded in `rankset` on
+/// `criterion` (`None` for the default question), finished or not
+pub fn leaderboard(rankset: &RankSet, criterion: Option<&str>) -> Leaderboard {
+    let rankings: Vec<_> = rankset
+        .rankings
+        .iter()
+        .filter(|r| r.criterion.as_deref() == criterion)
+        .collect();
+
+    let comparisons: Vec<(&Comparison, f64)> = rankings
+        .iter()
+        .flat_map(|r| {
+            r.session
+                .comparisons
+                .iter()
+                .map(|c| (c, r.answer_weight(c)))
+        })
+        .collect();
+    let answered: Vec<_> = rankings
+        .iter()
+        .filter(|r| !r.session.comparisons.is_empty())
+        .collect();
+    let cleared_sessions = rankings
+        .iter()
+        .filter(|r| r.session.comparisons.is_empty() && r.session.summary.is_some())
+        .count();
+
+    let mut items: Vec<Id> = rankset.items.values().map(|i| i.id.clone()).collect();
+    items.sort();
+
+    Leaderboard {
+        ratings: fit(&items, &comparisons),
+        comparisons: comparisons.len(),
+        users: answered
+            .iter()
+            .map(|r| &r.user_id)
+            .collect::<HashSet<_>>()
+            .len(),
+        sessions: answered.len(),
+        cleared_sessions,
+    }
+}
+
+/// Fit a Bradley–Terry rating for each of `items`, highest first
+///
+/// Each comparison counts as a game of the given weight, 1 for a full
+/// answer. Comparisons involving other items are ignored.
+pub fn fit(items: &[Id], comparisons: &[(&Comparison, f64)]) -> Vec<PooledRating> {
+    let n = items.len();
+    let index: HashMap<&Id, usize> = items.iter().enumerate().map(|(i, id)| (id, i)).collect();
+
+    // Games between each pair, and each item's wins
+    let mut games: BTreeMap<(usize, usize), f64> = BTreeMap::new();
+    let mut wins = vec![0.0; n];
+    let mut records = vec![WinLoss::default(); n];
+    for &(comparison, weight) in comparisons {
+        let (Some(&a), Some(&b)) = (index.get(&comparison.a), index.get(&comparison.b)) else {
+            continue;
+        };
+        if a == b {
+            continue;
+        }
+        let (winner, loser) = if comparison.winner == comparison.a {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        *games.entry((a.min(b), a.max(b))).or_default() += weight;
+        wins[winner] += weight;
+        records[winner].wins += 1;
+        records[loser].losses += 1;
+    }
+
+    // Strengths relative to the average item, whose strength is 1
+    let mut strength = vec![1.0; n];
+    for _ in 0..MAX_ITERATIONS {
+        let mut denominator: Vec<f64> = strength
+            .iter()
+            .map(|&p| 2.0 * PRIOR_GAMES / (p + 1.0))
+            .collect();
+        for (&(i, j), &count) in &games {
+            let shared = count / (strength[i] + strength[j]);
+            denominator[i] += shared;
+            denominator[j] += shared;
+        }
+
+        let mut largest_change: f64 = 0.0;
+        for i in 0..n {
+            let updated = (wins[i] + PRIOR_GAMES) / denominator[i];
+            largest_change = largest_change.max((updated / strength[i]).ln().abs());
+            strength[i] = updated;
+        }
+        if largest_change < TOLERANCE {
+            break;
+        }
+    }
+
+    // Information matrix of the log strengths
+    let mut information = vec![vec![0.0; n]; n];
+    for (i, &p) in strength.iter().enumerate() {
+        information[i][i] += 2.0 * PRIOR_GAMES * p / (p + 1.0).powi(2);
+    }
+    for (&(i, j), &count) in &games {
+        let (p, q) = (strength[i], strength[j]);
+        let weight = count * p * q / (p + q).powi(2);
+        information[i][i] += weight;
+        information[j][j] += weight;
+        information[i][j] -= weight;
+        information[j][i] -= weight;
+    }
+    let variance = inverse_diagonal(information);
+
+    let mut ratings: Vec<PooledRating> = items
+        .iter()
+        .enumerate()
+        .map(|(i, id)| PooledRating {
+            item: id.clone(),
+            rating: RATING_CENTER + strength[i].ln() * ELO_PER_LOG,
+            std_error: variance[i].sqrt() * ELO_PER_LOG,
+            record: records[i],
+        })
+        .collect();
+    ratings.sort_by(|a, b| {
+        b.rating
+            .total_cmp(&a.rating)
+            .then_with(|| a.item.cmp(&b.item))
+    });
+    ratings
+}
+
+/// Diagonal of the inverse of a symmetric positive definite matrix, by
+/// Gauss–Jordan elimination
+fn inverse_diagonal(mut matrix: Vec<Vec<f64>>) -> Vec<f64> {
+    let n = matrix.len();
+    let mut inverse: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for column in 0..n {
+        let pivot = matrix[column][column];
+        for j in 0..n {
+            matrix[column][j] /= pivot;
+            inverse[column][j] /= pivot;
+        }
+        for row in 0..n {
+            let factor = matrix[row][column];
+            if row == column || factor == 0.0 {
+                continue;
+            }
+            for j in 0..n {
+                matrix[row][j] -= factor * matrix[column][j];
+                inverse[row][j] -= factor * inverse[column][j];
+            }
+        }
+    }
+    (0..n).map(|i| inverse[i][i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Item, Ranking, User};
+
+    fn comparison(winner: &str, loser: &str) -> Comparison {
+        Comparison {
+            a: Id::from(winner),
+            b: Id::from(loser),
+            winner: Id::from(winner),
+            timestamp: chrono::Utc::now(),
+            shown_first: None,
+            latency_ms: None,
+        }
+    }
+
+    fn ids(names: &[&str]) -> Vec<Id> {
+        names.iter().map(|n| Id::from(*n)).collect()
+    }
+
+    #[test]
+    fn test_fit() {
+        let mut comparisons = Vec::new();
+        for _ in 0..3 {
+            comparisons.push(comparison("a", "b"));
+            comparisons.push(comparison("b", "c"));
+            comparisons.push(comparison("a", "c"));
+        }
+        let refs: Vec<_> = comparisons.iter().map(|c| (c, 1.0)).collect();
+        let ratings = fit(&ids(&["a", "b", "c", "d"]), &refs);
+
+        let order: Vec<&str> = ratings
+            .iter()
+            .map(|r| r.item.as_str())
+            .filter(|&id| id != "d")
+            .collect();
+        assert_eq!(order, vec!["a", "b", "c"]);
+
+        // b won and lost as often as the average item, and a and c mirror
+        // each other
+        let rating = |name: &str| ratings.iter().find(|r| r.item.as_str() == name).unwrap();
+        assert!((rating("b").rating - RATING_CENTER).abs() < 1e-6);
+        assert!((rating("a").rating + rating("c").rating - 2.0 * RATING_CENTER).abs() < 1e-6);
+        assert_eq!(rating("a").record, WinLoss { wins: 6, losses: 0 });
+
+        // d was never compared: it sits in the middle, least certain
+        let d = rating("d");
+        assert_eq!(d.rating, RATING_CENTER);
+        assert!((d.std_error - 2f64.sqrt() * ELO_PER_LOG).abs() < 1e-6);
+        assert!(ratings.iter().all(|r| r.std_error <= d.std_error));
+        let (low, high) = d.interval(1.0);
+        assert!((high - low - 2.0 * d.std_error).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_leaderboard_pools_users() {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        for name in ["a", "b", "c"] {
+            let item = Item::with_id(Id::from(name), name.to_string(), chrono::Utc::now());
+            rankset.add_item(item).unwrap();
+        }
+        // Each user only compared one pair; together they give a full order
+        for (i, (winner, loser)) in [("a", "b"), ("b", "c")].iter().enumerate() {
+            let user = User::new(format!("user{}", i), None);
+            let mut ranking = Ranking::new(user.id.clone(), "merge".to_string());
+            for _ in 0..2 {
+                ranking.session.comparisons.push(comparison(winner, loser));
+            }
+            rankset.add_user(user).unwrap();
+            rankset.rankings.push(ranking);
+        }
+        let user = User::new("cleared".to_string(), None);
+        let mut cleared = Ranking::new(user.id.clone(), "merge".to_string());
+        cleared.session.comparisons.push(comparison("c", "a"));
+        cleared.session.clear_comparisons();
+        cleared.session.info.complete();
+        // Finished without ever comparing, e.g. a tier list: nothing was lost
+        let mut tiers_only = Ranking::new(user.id.clone(), "merge".to_string());
+        tiers_only.session.info.complete();
+        rankset.add_user(user).unwrap();
+        rankset.rankings.push(cleared);
+        rankset.rankings.push(tiers_only);
+
+        let board = leaderboard(&rankset, None);
+        let order: Vec<&str> = board.ratings.iter().map(|r| r.item.as_str()).collect();
+        assert_eq!(order, vec!["a", "b", "c"]);
+        assert_eq!(
+            (
+                board.comparisons,
+                board.users,
+                board.sessions,
+                board.cleared_sessions
+            ),
+            (4, 2, 2, 1)
+        );
+
+        // Weak answers move ratings less
+        let slow = |latency| {
+            let mut c = comparison("c", "a");
+            c.latency_ms = Some(latency);
+            c
+        };
+        let mut weak = rankset.rankings[0].clone();
+        weak.weak_after_ms = Some(5000);
+        weak.session.comparisons = vec![slow(9000)];
+        let mut strong = weak.clone();
+        strong.session.comparisons = vec![slow(1000)];
+        let c_rating = |extra: &Ranking| {
+            let mut rankset = rankset.clone();
+            rankset.rankings.push(extra.clone());
+            let board = leaderboard(&rankset, None);
+            board
+                .ratings
+                .iter()
+                .find(|r| r.item.as_str() == "c")
+                .unwrap()
+                .rating
+        };
+        assert!(c_rating(&weak) < c_rating(&strong));
+
+        // Nothing was answered on another criterion
+        let other = leaderboard(&rankset, Some("taste"));
+        assert_eq!(other.comparisons, 0);
+        assert!(other.ratings.iter().all(|r| r.rating == RATING_CENTER));
+    }
+}
@@ -0,0 +1,105 @@
+//! Which item of a pair is shown first, and whether rankers favor it
+//!
+//! Strategies always hand out pairs in the same order, so showing that order
+//! as-is would put the same kind of item in front every time. Each question
+//! is instead flipped at random from the ranking's seed, which keeps the
+//! display reproducible when a session is resumed.
+
+use crate::seed::{self, Stream};
+use crate::session::Comparison;
+use crate::Id;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Pick which of `a` and `b` to show first for the `index`-th question of a
+/// ranking with the given seed
+pub fn shown_first<'a>(seed: u64, index: usize, a: &'a Id, b: &'a Id) -> &'a Id {
+    let mut rng = seed::rng(seed, Stream::Display, index as u64);
+    if rng.random_bool(0.5) {
+        b
+    } else {
+        a
+    }
+}
+
+/// How often the item shown first was picked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PositionBias {
+    /// Answers picking the item shown first
+    pub first: usize,
+    /// Answers picking the item shown second
+    pub second: usize,
+}
+
+impl PositionBias {
+    /// Count answers from comparisons whose display order was recorded
+    pub fn from_comparisons<'a>(comparisons: impl IntoIterator<Item = &'a Comparison>) -> Self {
+        let mut bias = Self::default();
+        for comparison in comparisons {
+            match &comparison.shown_first {
+                Some(first) if *first == comparison.winner => bias.first += 1,
+                Some(_) => bias.second += 1,
+                None => {}
+            }
+        }
+        bias
+    }
+
+    pub fn total(&self) -> usize {
+        self.first + self.second
+    }
+
+    /// Fraction of answers picking the first option, if any were recorded
+    pub fn first_rate(&self) -> Option<f64> {
+        (self.total() > 0).then(|| self.first as f64 / self.total() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Session;
+
+    #[test]
+    fn test_shown_first_is_reproducible() {
+        let a = Id::from("a");
+        let b = Id::from("b");
+
+        let run = |seed| -> Vec<Id> {
+            (0..32)
+                .map(|i| shown_first(seed, i, &a, &b).clone())
+                .collect()
+        };
+        assert_eq!(run(7), run(7));
+
+        // Both orders come up
+        let firsts = run(7);
+        assert!(firsts.contains(&a));
+        assert!(firsts.contains(&b));
+    }
+
+    #[test]
+    fn test_position_bias() {
+        let a = Id::from("a");
+        let b = Id::from("b");
+        let mut session = Session::new();
+
+        session
+            .add_comparison(a.clone(), b.clone(), a.clone())
+            .shown_first = Some(a.clone());
+        session
+            .add_comparison(a.clone(), b.clone(), b.clone())
+            .shown_first = Some(b.clone());
+        session
+            .add_comparison(a.clone(), b.clone(), a.clone())
+            .shown_first = Some(b.clone());
+        // Older comparisons without a recorded order are skipped
+        session.add_comparison(a.clone(), b.clone(), a);
+
+        let bias = PositionBias::from_comparisons(&session.comparisons);
+        assert_eq!(bias.first, 2);
+        assert_eq!(bias.second, 1);
+        assert!((bias.first_rate().unwrap() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(PositionBias::default().first_rate(), None);
+    }
+}
@@ -0,0 +1,150 @@
+//! How comparisons are worded
+//!
+//! "Which is better?" does not fit every list. A rankset can set its own
+//! question, labels for the two options and a template for showing items.
+
+use crate::Item;
+use serde::{Deserialize, Serialize};
+
+/// Question asked when no other is set
+pub const DEFAULT_QUESTION: &str = "Which is better?";
+
+/// Rankset-level prompt settings
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Prompt {
+    /// Question shown for every comparison
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub question: Option<String>,
+    /// Labels for the first and second option, e.g. "Left" and "Right"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub labels: Option<[String; 2]>,
+    /// How to show an item, e.g. "{value} ({group})"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_template: Option<String>,
+}
+
+impl Prompt {
+    /// Whether nothing has been customized
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Question to ask, falling back to the default one
+    pub fn question(&self) -> &str {
+        self.question.as_deref().unwrap_or(DEFAULT_QUESTION)
+    }
+
+    /// Label of option 1 or 2, if labels are set
+    pub fn label(&self, choice: u8) -> Option<&str> {
+        let labels = self.labels.as_ref()?;
+        match choice {
+            1 => Some(&labels[0]),
+            2 => Some(&labels[1]),
+            _ => None,
+        }
+    }
+
+    /// Which option an answer picks: "1", "2" or either label
+    pub fn parse_choice(&self, answer: &str) -> Option<u8> {
+        let answer = answer.trim();
+        match answer {
+            "1" => Some(1),
+            "2" => Some(2),
+            _ => [1, 2].into_iter().find(|&choice| {
+                self.label(choice)
+                    .is_some_and(|label| label.eq_ignore_ascii_case(answer))
+            }),
+        }
+    }
+
+    /// Show an item using the template
+    ///
+    /// `{name}` is replaced with the item field or attribute of that name
+    /// (see [`Item::field`]); a field the item lacks renders as empty, and
+    /// unknown names are left as written. Without a template the item's
+    /// value is shown.
+    pub fn render(&self, item: &Item) -> String {
+        let Some(template) = &self.item_template else {
+            return item.value.clone();
+        };
+
+        let mut rendered = String::new();
+        let mut rest = template.as_str();
+        while let Some(start) = rest.find('{') {
+            rendered.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let Some(len) = rest.find('}') else {
+                break;
+            };
+            let name = &rest[1..len];
+            match item.field(name) {
+                Some(value) => rendered.push_str(&value),
+                None if is_known_field(name) => {}
+                None => rendered.push_str(&rest[..=len]),
+            }
+            rest = &rest[len + 1..];
+        }
+        rendered.push_str(rest);
+        rendered
+    }
+}
+
+fn is_known_field(name: &str) -> bool {
+    matches!(
+        name,
+        "value" | "id" | "group" | "description" | "url" | "tags"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labeled() -> Prompt {
+        Prompt {
+            question: Some("Which would you rather watch tonight?".to_string()),
+            labels: Some(["Left".to_string(), "Right".to_string()]),
+            item_template: None,
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let prompt = Prompt::default();
+        assert!(prompt.is_default());
+        assert_eq!(prompt.question(), DEFAULT_QUESTION);
+        assert_eq!(prompt.label(1), None);
+        assert_eq!(prompt.parse_choice("2"), Some(2));
+        assert_eq!(prompt.parse_choice("left"), None);
+    }
+
+    #[test]
+    fn test_labels() {
+        let prompt = labeled();
+        assert_eq!(prompt.label(2), Some("Right"));
+        assert_eq!(prompt.parse_choice("left"), Some(1));
+        assert_eq!(prompt.parse_choice(" RIGHT "), Some(2));
+        assert_eq!(prompt.parse_choice("middle"), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut item = Item::new("Bills".to_string());
+        item.group = Some("AFC East".to_string());
+
+        let mut prompt = Prompt::default();
+        assert_eq!(prompt.render(&item), "Bills");
+
+        prompt.item_template = Some("{value} ({group})".to_string());
+        assert_eq!(prompt.render(&item), "Bills (AFC East)");
+
+        item.attributes
+            .insert("founded".to_string(), "1960".to_string());
+        prompt.item_template = Some("{value}, est. {founded} {url}{unknown}".to_string());
+        assert_eq!(prompt.render(&item), "Bills, est. 1960 {unknown}");
+
+        // An unclosed brace is kept as written
+        prompt.item_template = Some("{value} {value".to_string());
+        assert_eq!(prompt.render(&item), "Bills {value");
+    }
+}
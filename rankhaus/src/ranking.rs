@@ -1,25 +1,130 @@
+use crate::budget::Budget;
+use crate::constraint::Constraint;
+use crate::filter::ItemFilter;
+use crate::group::GroupMode;
+use crate::score::ScoreSheet;
+use crate::seed::{self, Stream};
+use crate::session::Comparison;
+use crate::strategy::{self, StrategyConfig};
+use crate::tier::TierList;
+use crate::timing;
 use crate::{Id, Session};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 pub use crate::strategy::RankResult;
 
+/// A change of strategy part way through a ranking session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategySwitch {
+    pub from: String,
+    pub to: String,
+    /// Number of comparisons recorded before the switch
+    pub after_comparisons: usize,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Where a warm start ordering came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum WarmStartSource {
+    /// The user's own earlier completed ranking
+    Previous { session_id: Id },
+    /// Consensus of all users' completed rankings
+    Consensus,
+}
+
+/// A prior ordering used to seed the strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarmStart {
+    #[serde(flatten)]
+    pub source: WarmStartSource,
+    /// Item IDs, best to worst
+    pub order: Vec<Id>,
+}
+
 /// A complete ranking with results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ranking {
     pub user_id: Id,
     pub strategy: String,
+    /// Options the strategy runs with; `None` for rankings made before
+    /// options existed, which used the defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_config: Option<StrategyConfig>,
+    /// Version of the strategy's algorithm when the ranking was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy_version: Option<u32>,
+    /// Criterion this ranking answers, if the rankset defines any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criterion: Option<String>,
+    /// Subset of items ranked; `None` means all items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<ItemFilter>,
     pub session: Session,
+    /// Seed that every random choice in the ranking is drawn from, see
+    /// [`crate::seed`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub switches: Vec<StrategySwitch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warm_start: Option<WarmStart>,
+    /// Known facts declared before ranking, applied again on resume
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<Constraint>,
+    /// Tier list settings when ranking in tier list mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier_list: Option<TierList>,
+    /// Score mode settings when items are scored before comparing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_sheet: Option<ScoreSheet>,
+    /// How groups are combined when ranking by item group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_mode: Option<GroupMode>,
+    /// Answers slower than this many milliseconds count as weak
+    /// preferences for rating strategies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weak_after_ms: Option<u64>,
+    /// Limit after which the ranking finishes with a best estimate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Budget>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<RankResult>,
 }
 
 impl Ranking {
-    /// Create a new ranking
+    /// Create a new ranking with a random seed
     pub fn new(user_id: Id, strategy: String) -> Self {
+        Self::with_seed(user_id, strategy, rand::random(), 0)
+    }
+
+    /// Create a new ranking whose random choices, including its session ID,
+    /// all follow from `seed`
+    ///
+    /// `replay` counts the rankings already made with the same seed, so each
+    /// replay of a seed within a rankset gets its own session ID.
+    pub fn with_seed(user_id: Id, strategy: String, seed: u64, replay: u64) -> Self {
+        let mut session = Session::new();
+        session.info.id = Id::generate(Some("s"), &mut seed::rng(seed, Stream::Ids, replay));
+
         Self {
+            strategy_config: StrategyConfig::default_for(&strategy).ok(),
+            strategy_version: strategy::version(&strategy),
             user_id,
             strategy,
-            session: Session::new(),
+            criterion: None,
+            filter: None,
+            session,
+            seed: Some(seed),
+            switches: Vec::new(),
+            warm_start: None,
+            constraints: Vec::new(),
+            tier_list: None,
+            score_sheet: None,
+            group_mode: None,
+            weak_after_ms: None,
+            budget: None,
             result: None,
         }
     }
@@ -28,4 +133,116 @@ impl Ranking {
     pub fn is_complete(&self) -> bool {
         self.result.is_some()
     }
+
+    /// Whether this ranking finished with every pair of items decided,
+    /// rather than stopped early by a budget or left as tiers without an
+    /// order within them
+    pub fn is_strict_order(&self) -> bool {
+        let Some(result) = &self.result else {
+            return false;
+        };
+        let unordered_tiers =
+            result.tiers.is_some() && !self.tier_list.as_ref().is_some_and(|t| t.order_within);
+        result.order.is_some() && result.confidence.is_none() && !unordered_tiers
+    }
+
+    /// Whether this ranking answers `criterion` over the items `filter`
+    /// picks (`None` for either meaning the default question or all items)
+    pub fn in_scope(&self, criterion: Option<&str>, filter: Option<&ItemFilter>) -> bool {
+        self.criterion.as_deref() == criterion && self.filter.as_ref() == filter
+    }
+
+    /// Weight to give a comparison: weak if it was answered slowly and
+    /// slow answers count as weak preferences
+    pub fn answer_weight(&self, comparison: &Comparison) -> f64 {
+        match (self.weak_after_ms, comparison.latency_ms) {
+            (Some(limit), Some(latency)) if latency > limit => timing::WEAK_PREFERENCE_WEIGHT,
+            _ => 1.0,
+        }
+    }
+
+    /// Continue this ranking with a different strategy and options
+    ///
+    /// Comparisons recorded so far stay in the session and should be fed
+    /// to the new strategy with `RankStrategy::record_prior`.
+    pub fn switch_strategy(&mut self, config: StrategyConfig) {
+        let strategy = config.name().to_string();
+        self.strategy_version = strategy::version(&strategy);
+        self.strategy_config = Some(config);
+        self.switches.push(StrategySwitch {
+            from: std::mem::replace(&mut self.strategy, strategy.clone()),
+            to: strategy,
+            after_comparisons: self.session.comparisons.len(),
+            timestamp: Utc::now(),
+        });
+        self.session.info.touch();
+    }
+}
+
+/// A completed "merge" ranking by `user_id` with the given order, best
+/// first, for tests of reports over many rankings
+#[cfg(test)]
+pub(crate) fn completed_ranking(user_id: &Id, order: &[Id]) -> Ranking {
+    let mut ranking = Ranking::new(user_id.clone(), "merge".to_string());
+    ranking.result = Some(crate::RankResult {
+        order: Some(order.to_vec()),
+        ..Default::default()
+    });
+    ranking.session.info.complete();
+    ranking
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "elo")]
+    fn test_switch_strategy() {
+        let mut ranking = Ranking::new(Id::from("u1"), "quicksort".to_string());
+        ranking
+            .session
+            .add_comparison(Id::from("a"), Id::from("b"), Id::from("a"));
+
+        ranking.switch_strategy(StrategyConfig::default_for("elo").unwrap());
+
+        assert_eq!(ranking.strategy, "elo");
+        assert_eq!(ranking.switches.len(), 1);
+        assert_eq!(ranking.switches[0].from, "quicksort");
+        assert_eq!(ranking.switches[0].to, "elo");
+        assert_eq!(ranking.switches[0].after_comparisons, 1);
+        assert_eq!(ranking.session.comparisons.len(), 1);
+        assert_eq!(ranking.strategy_config.as_ref().unwrap().name(), "elo");
+        assert_eq!(ranking.strategy_version, strategy::version("elo"));
+    }
+
+    #[test]
+    fn test_with_seed() {
+        let a = Ranking::with_seed(Id::from("u1"), "merge".to_string(), 11, 0);
+        let b = Ranking::with_seed(Id::from("u1"), "merge".to_string(), 11, 0);
+        let c = Ranking::with_seed(Id::from("u1"), "merge".to_string(), 12, 0);
+        let replay = Ranking::with_seed(Id::from("u1"), "merge".to_string(), 11, 1);
+        assert_eq!(a.session.info.id, b.session.info.id);
+        assert_ne!(a.session.info.id, c.session.info.id);
+        assert_ne!(a.session.info.id, replay.session.info.id);
+        assert_eq!(replay.seed, Some(11));
+    }
+
+    #[test]
+    fn test_answer_weight() {
+        let mut ranking = Ranking::new(Id::from("u1"), "elo".to_string());
+        let comparison =
+            ranking
+                .session
+                .add_comparison(Id::from("a"), Id::from("b"), Id::from("a"));
+        comparison.latency_ms = Some(9000);
+        let comparison = comparison.clone();
+
+        assert_eq!(ranking.answer_weight(&comparison), 1.0);
+        ranking.weak_after_ms = Some(5000);
+        assert_eq!(
+            ranking.answer_weight(&comparison),
+            timing::WEAK_PREFERENCE_WEIGHT
+        );
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{Error, Item, Ranking, Result, User};
+use crate::aggregate::{self, Method};
+use crate::criteria::Criterion;
+use crate::filter::ItemFilter;
+use crate::prompt::Prompt;
+use crate::{Error, Id, Item, Ranking, Result, User};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -12,6 +16,16 @@ pub struct RankSetMeta {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub created: DateTime<Utc>,
+    /// Criteria items can be ranked on; empty means a single implicit one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criteria: Vec<Criterion>,
+    /// How comparisons are worded
+    #[serde(default, skip_serializing_if = "Prompt::is_default")]
+    pub prompt: Prompt,
+    /// Keep each finished session's comparisons instead of clearing them,
+    /// so they can be pooled into global ratings
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub keep_comparisons: bool,
 }
 
 /// A complete ranking set with items, users, and rankings
@@ -35,6 +49,9 @@ impl RankSet {
                 author,
                 description,
                 created: Utc::now(),
+                criteria: Vec::new(),
+                prompt: Prompt::default(),
+                keep_comparisons: false,
             },
             users: HashMap::new(),
             items: HashMap::new(),
@@ -192,11 +209,87 @@ impl RankSet {
         self.items.remove(&item_id);
         Ok(())
     }
+
+    /// Get a criterion by name (case insensitive)
+    pub fn get_criterion(&self, name: &str) -> Result<&Criterion> {
+        self.meta
+            .criteria
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::Other(format!("Criterion not found: {}", name)))
+    }
+
+    /// Add a ranking criterion
+    pub fn add_criterion(&mut self, criterion: Criterion) -> Result<()> {
+        if self.get_criterion(&criterion.name).is_ok() {
+            return Err(Error::Other(format!(
+                "Duplicate criterion: {}",
+                criterion.name
+            )));
+        }
+        self.meta.criteria.push(criterion);
+        Ok(())
+    }
+
+    /// Remove a ranking criterion by name; rankings tagged with it are kept
+    pub fn remove_criterion(&mut self, name: &str) -> Result<Criterion> {
+        let index = self
+            .meta
+            .criteria
+            .iter()
+            .position(|c| c.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::Other(format!("Criterion not found: {}", name)))?;
+        Ok(self.meta.criteria.remove(index))
+    }
+
+    /// Get a user's most recently completed ranking on a criterion, or on
+    /// the implicit question if `criterion` is `None`
+    ///
+    /// Only rankings of the same subset count: `filter` is `None` for
+    /// rankings of all items.
+    pub fn latest_ranking(
+        &self,
+        user_id: &Id,
+        criterion: Option<&str>,
+        filter: Option<&ItemFilter>,
+    ) -> Option<&Ranking> {
+        self.rankings
+            .iter()
+            .filter(|r| &r.user_id == user_id && r.is_complete())
+            .filter(|r| r.in_scope(criterion, filter))
+            .max_by_key(|r| r.session.info.completed)
+    }
+
+    /// Consensus order of the current items on a criterion and subset, best
+    /// to worst
+    ///
+    /// Items are ordered by their average position in each user's most
+    /// recently completed ranking. Items nobody has ranked are left out.
+    /// Returns `None` if there are no completed rankings.
+    pub fn consensus_order(
+        &self,
+        criterion: Option<&str>,
+        filter: Option<&ItemFilter>,
+    ) -> Option<Vec<Id>> {
+        let latest = self
+            .users
+            .values()
+            .filter_map(|user| self.latest_ranking(&user.id, criterion, filter));
+        let orders = aggregate::current_orders(self, latest);
+        if orders.is_empty() {
+            return None;
+        }
+
+        let orders: Vec<&[Id]> = orders.iter().map(|o| o.as_slice()).collect();
+        let consensus = aggregate::aggregate_orders(&orders, Method::AverageRank);
+        Some(consensus.standings.into_iter().map(|s| s.item).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ranking::completed_ranking;
 
     #[test]
     fn test_rankset_creation() {
@@ -317,6 +410,106 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_latest_ranking() {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        let user = User::new("alice".to_string(), None);
+        let user_id = user.id.clone();
+        rankset.add_user(user).unwrap();
+        let a = Id::from("a");
+        let b = Id::from("b");
+
+        assert!(rankset.latest_ranking(&user_id, None, None).is_none());
+
+        rankset
+            .rankings
+            .push(completed_ranking(&user_id, &[a.clone(), b.clone()]));
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        rankset
+            .rankings
+            .push(completed_ranking(&user_id, &[b.clone(), a.clone()]));
+        rankset
+            .rankings
+            .push(Ranking::new(user_id.clone(), "merge".to_string()));
+
+        let latest = rankset.latest_ranking(&user_id, None, None).unwrap();
+        assert_eq!(latest.result.as_ref().unwrap().order, Some(vec![b, a]));
+    }
+
+    #[test]
+    fn test_criteria() {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        rankset
+            .add_criterion(Criterion::new("taste".to_string(), None))
+            .unwrap();
+        assert!(rankset
+            .add_criterion(Criterion::new("Taste".to_string(), None))
+            .is_err());
+        assert!(rankset.get_criterion("TASTE").is_ok());
+
+        let user_id = Id::from("u1");
+        let a = Id::from("a");
+        let b = Id::from("b");
+        let mut taste = completed_ranking(&user_id, &[a.clone(), b.clone()]);
+        taste.criterion = Some("taste".to_string());
+        rankset.rankings.push(taste);
+
+        assert!(rankset.latest_ranking(&user_id, None, None).is_none());
+        assert!(rankset
+            .latest_ranking(&user_id, Some("taste"), None)
+            .is_some());
+
+        let mut subset = completed_ranking(&user_id, std::slice::from_ref(&a));
+        subset.criterion = Some("taste".to_string());
+        subset.filter = Some(ItemFilter::new(
+            vec!["sweet".to_string()],
+            Default::default(),
+            vec![],
+        ));
+        rankset.rankings.push(subset);
+        let latest = rankset
+            .latest_ranking(&user_id, Some("taste"), None)
+            .unwrap();
+        assert!(latest.filter.is_none());
+        let filter = ItemFilter::new(vec!["Sweet".to_string()], Default::default(), vec![]);
+        assert!(rankset
+            .latest_ranking(&user_id, Some("taste"), Some(&filter))
+            .is_some());
+
+        rankset.remove_criterion("taste").unwrap();
+        assert!(rankset.get_criterion("taste").is_err());
+        assert!(rankset.remove_criterion("taste").is_err());
+    }
+
+    #[test]
+    fn test_consensus_order() {
+        let mut rankset = RankSet::new("test".to_string(), "author".to_string(), None);
+        assert!(rankset.consensus_order(None, None).is_none());
+
+        let items: Vec<Item> = ["red", "green", "blue"]
+            .iter()
+            .map(|v| Item::new(v.to_string()))
+            .collect();
+        let ids: Vec<Id> = items.iter().map(|i| i.id.clone()).collect();
+        for item in items {
+            rankset.add_item(item).unwrap();
+        }
+
+        for (name, order) in [
+            ("alice", [0, 1, 2]),
+            ("bob", [1, 0, 2]),
+            ("carol", [0, 2, 1]),
+        ] {
+            let user = User::new(name.to_string(), None);
+            let order: Vec<Id> = order.iter().map(|&i| ids[i].clone()).collect();
+            rankset.rankings.push(completed_ranking(&user.id, &order));
+            rankset.add_user(user).unwrap();
+        }
+
+        let consensus = rankset.consensus_order(None, None).unwrap();
+        assert_eq!(consensus, ids);
+    }
+
     #[test]
     fn test_save_and_load() {
         use std::fs;
@@ -0,0 +1,144 @@
+//! Score mode: rate items directly, then refine with pairwise comparisons
+//!
+//! Scoring each item once is much faster than comparing pairs on a long
+//! list. Pairs whose scores differ by more than the tolerance are decided by
+//! the scores; only items with equal or close scores are compared.
+
+use crate::{Error, Id, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lowest score an item can get
+pub const MIN_SCORE: u8 = 1;
+
+/// Highest score an item can get
+pub const MAX_SCORE: u8 = 10;
+
+/// Score mode settings and the scores given so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreSheet {
+    /// Items whose scores differ by at most this much are compared directly
+    pub tolerance: u8,
+    /// Score given to each item so far
+    #[serde(default)]
+    pub scores: BTreeMap<Id, u8>,
+}
+
+impl ScoreSheet {
+    pub fn new(tolerance: u8) -> Self {
+        Self {
+            tolerance,
+            scores: BTreeMap::new(),
+        }
+    }
+
+    /// Record an item's score
+    pub fn set(&mut self, item: Id, score: u8) -> Result<()> {
+        if !(MIN_SCORE..=MAX_SCORE).contains(&score) {
+            return Err(Error::Other(format!(
+                "Score must be between {} and {}",
+                MIN_SCORE, MAX_SCORE
+            )));
+        }
+        self.scores.insert(item, score);
+        Ok(())
+    }
+
+    /// Items from `items` that have not been scored yet
+    pub fn unscored(&self, items: &[Id]) -> Vec<Id> {
+        items
+            .iter()
+            .filter(|id| !self.scores.contains_key(id))
+            .cloned()
+            .collect()
+    }
+
+    /// Every (winner, loser) pair decided by the scores alone
+    pub fn implied_pairs(&self) -> Vec<(Id, Id)> {
+        let mut pairs = Vec::new();
+        for (a, score_a) in &self.scores {
+            for (b, score_b) in &self.scores {
+                if *score_a > score_b.saturating_add(self.tolerance) {
+                    pairs.push((a.clone(), b.clone()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Scored items split into runs whose neighbouring scores are within the
+    /// tolerance, highest scores first
+    ///
+    /// Only items in the same run can need a direct comparison.
+    pub fn close_groups(&self) -> Vec<Vec<Id>> {
+        let mut sorted: Vec<(&Id, u8)> = self.scores.iter().map(|(id, s)| (id, *s)).collect();
+        sorted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+        let mut groups: Vec<Vec<Id>> = Vec::new();
+        let mut previous: Option<u8> = None;
+        for (id, score) in sorted {
+            match (previous, groups.last_mut()) {
+                (Some(prev), Some(group)) if prev - score <= self.tolerance => {
+                    group.push(id.clone())
+                }
+                _ => groups.push(vec![id.clone()]),
+            }
+            previous = Some(score);
+        }
+        groups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sheet(tolerance: u8, scores: &[(&str, u8)]) -> ScoreSheet {
+        let mut sheet = ScoreSheet::new(tolerance);
+        for (id, score) in scores {
+            sheet.set(Id::from(*id), *score).unwrap();
+        }
+        sheet
+    }
+
+    #[test]
+    fn test_set_rejects_out_of_range() {
+        let mut sheet = ScoreSheet::new(1);
+        assert!(sheet.set(Id::from("a"), 0).is_err());
+        assert!(sheet.set(Id::from("a"), 11).is_err());
+        assert!(sheet.set(Id::from("a"), 10).is_ok());
+        assert_eq!(
+            sheet.unscored(&[Id::from("a"), Id::from("b")]),
+            vec![Id::from("b")]
+        );
+    }
+
+    #[test]
+    fn test_implied_pairs_skip_close_scores() {
+        let pairs = sheet(1, &[("a", 9), ("b", 8), ("c", 5)]).implied_pairs();
+
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.contains(&(Id::from("a"), Id::from("c"))));
+        assert!(pairs.contains(&(Id::from("b"), Id::from("c"))));
+
+        // With no tolerance only equal scores are left open
+        let pairs = sheet(0, &[("a", 9), ("b", 8), ("c", 5)]).implied_pairs();
+        assert_eq!(pairs.len(), 3);
+    }
+
+    #[test]
+    fn test_close_groups() {
+        let scored = sheet(
+            1,
+            &[("a", 9), ("b", 8), ("c", 5), ("d", 4), ("e", 3), ("f", 1)],
+        );
+        assert_eq!(
+            scored.close_groups(),
+            vec![
+                vec![Id::from("a"), Id::from("b")],
+                vec![Id::from("c"), Id::from("d"), Id::from("e")],
+                vec![Id::from("f")],
+            ]
+        );
+    }
+}
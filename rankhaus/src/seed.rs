@@ -0,0 +1,64 @@
+//! Reproducible randomness for rankings
+//!
+//! Everything random about a ranking is drawn from the one seed recorded on
+//! it: its session ID, the order pairs are shown in, which pairs are probed
+//! and the strategy's own choices. Each use gets its own stream, so adding
+//! draws to one doesn't shift the others, and the same seed replays the same
+//! session.
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// What a random draw is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    /// Session IDs
+    Ids,
+    /// Which item of a pair is shown first
+    Display,
+    /// Which pair is re-asked as a consistency probe
+    Probes,
+    /// Strategy choices such as quicksort pivots
+    Strategy,
+    /// Simulated rankers and their true orders
+    Simulation,
+}
+
+/// A generator for the `index`-th draw of `stream` under `seed`
+pub fn rng(seed: u64, stream: Stream, index: u64) -> StdRng {
+    let stream = match stream {
+        Stream::Ids => 1,
+        Stream::Display => 2,
+        Stream::Probes => 3,
+        Stream::Strategy => 4,
+        Stream::Simulation => 5,
+    };
+    StdRng::seed_from_u64(mix(mix(seed ^ stream) ^ index))
+}
+
+/// SplitMix64 finalizer, so nearby seeds and indexes give unrelated streams
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draws(seed: u64, stream: Stream, index: u64) -> Vec<u32> {
+        let mut rng = rng(seed, stream, index);
+        (0..4).map(|_| rng.random()).collect()
+    }
+
+    #[test]
+    fn test_streams_are_reproducible_and_distinct() {
+        assert_eq!(draws(42, Stream::Display, 3), draws(42, Stream::Display, 3));
+        assert_ne!(draws(42, Stream::Display, 3), draws(42, Stream::Probes, 3));
+        assert_ne!(draws(42, Stream::Display, 3), draws(42, Stream::Display, 4));
+        assert_ne!(draws(42, Stream::Display, 3), draws(43, Stream::Display, 3));
+    }
+}
@@ -1,6 +1,11 @@
+use crate::consistency;
+use crate::item_stats::WinLoss;
+use crate::presentation::PositionBias;
+use crate::timing::LatencyStats;
 use crate::Id;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Status of a ranking session
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -62,6 +67,91 @@ pub struct Comparison {
     pub b: Id,
     pub winner: Id,
     pub timestamp: DateTime<Utc>,
+    /// Item displayed as option 1, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shown_first: Option<Id>,
+    /// Time taken to answer, in milliseconds, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+/// How a consistency probe pair was chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeKind {
+    /// A pair that was already answered, asked again
+    Repeat,
+    /// A pair never asked whose answer is implied by transitivity
+    Transitive,
+}
+
+/// A consistency check asked during ranking
+///
+/// Probes are not fed to the strategy; they only record whether the
+/// ranker's answer matches what earlier comparisons imply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Probe {
+    pub a: Id,
+    pub b: Id,
+    /// Winner implied by earlier comparisons
+    pub expected: Id,
+    /// Winner actually chosen
+    pub answer: Id,
+    pub kind: ProbeKind,
+    pub timestamp: DateTime<Utc>,
+    /// Item displayed as option 1, if recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shown_first: Option<Id>,
+}
+
+impl Probe {
+    /// Check if the answer agrees with earlier comparisons
+    pub fn is_consistent(&self) -> bool {
+        self.answer == self.expected
+    }
+
+    /// The probe's answer as a plain comparison
+    pub fn as_comparison(&self) -> Comparison {
+        Comparison {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            winner: self.answer.clone(),
+            timestamp: self.timestamp,
+            shown_first: self.shown_first.clone(),
+            latency_ms: None,
+        }
+    }
+}
+
+/// Figures worked out from a session's answers
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    /// How often the item shown first was picked, probes included
+    pub position_bias: PositionBias,
+    /// How long answers took
+    pub latency: LatencyStats,
+    /// Each item's wins and losses
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub records: BTreeMap<Id, WinLoss>,
+    /// Preference cycles among the comparisons and probe answers
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cycles: Vec<Vec<Id>>,
+}
+
+impl SessionSummary {
+    pub fn new(comparisons: &[Comparison], probes: &[Probe]) -> Self {
+        let answers: Vec<Comparison> = comparisons
+            .iter()
+            .cloned()
+            .chain(probes.iter().map(Probe::as_comparison))
+            .collect();
+        Self {
+            position_bias: PositionBias::from_comparisons(&answers),
+            latency: LatencyStats::from_comparisons(comparisons),
+            records: WinLoss::from_comparisons(comparisons),
+            cycles: consistency::find_cycles(&answers),
+        }
+    }
 }
 
 /// A complete ranking session
@@ -70,6 +160,11 @@ pub struct Session {
     #[serde(flatten)]
     pub info: SessionInfo,
     pub comparisons: Vec<Comparison>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub probes: Vec<Probe>,
+    /// What the comparisons showed, kept once they are cleared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<SessionSummary>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<serde_json::Value>,
 }
@@ -80,19 +175,98 @@ impl Session {
         Self {
             info: SessionInfo::new(),
             comparisons: Vec::new(),
+            probes: Vec::new(),
+            summary: None,
             state: None,
         }
     }
 
     /// Add a comparison to the session
-    pub fn add_comparison(&mut self, a: Id, b: Id, winner: Id) {
+    ///
+    /// Returns the new comparison so details such as the display order can
+    /// be filled in.
+    pub fn add_comparison(&mut self, a: Id, b: Id, winner: Id) -> &mut Comparison {
         self.comparisons.push(Comparison {
             a,
             b,
             winner,
             timestamp: Utc::now(),
+            shown_first: None,
+            latency_ms: None,
         });
         self.info.touch();
+        self.comparisons.last_mut().unwrap()
+    }
+
+    /// Add a consistency probe answer to the session
+    ///
+    /// Returns the new probe so details such as the display order can be
+    /// filled in.
+    pub fn add_probe(
+        &mut self,
+        a: Id,
+        b: Id,
+        expected: Id,
+        answer: Id,
+        kind: ProbeKind,
+    ) -> &mut Probe {
+        self.probes.push(Probe {
+            a,
+            b,
+            expected,
+            answer,
+            kind,
+            timestamp: Utc::now(),
+            shown_first: None,
+        });
+        self.info.touch();
+        self.probes.last_mut().unwrap()
+    }
+
+    /// The kept summary, or one worked out from the comparisons
+    pub fn summary(&self) -> SessionSummary {
+        self.summary
+            .clone()
+            .unwrap_or_else(|| SessionSummary::new(&self.comparisons, &self.probes))
+    }
+
+    /// How often the item shown first was picked in this session, counting
+    /// probes as well as comparisons
+    pub fn position_bias(&self) -> PositionBias {
+        self.summary().position_bias
+    }
+
+    /// How long answers in this session took
+    pub fn latency_stats(&self) -> LatencyStats {
+        self.summary().latency
+    }
+
+    /// Each item's wins and losses in this session
+    pub fn item_records(&self) -> BTreeMap<Id, WinLoss> {
+        self.summary().records
+    }
+
+    /// Preference cycles in this session's answers, probes included
+    pub fn cycles(&self) -> Vec<Vec<Id>> {
+        self.summary().cycles
+    }
+
+    /// Drop the comparisons to save space, keeping their summary
+    pub fn clear_comparisons(&mut self) {
+        if !self.comparisons.is_empty() {
+            self.summary = Some(SessionSummary::new(&self.comparisons, &self.probes));
+        }
+        self.comparisons.clear();
+    }
+
+    /// Fraction of probes answered consistently, if any probes were asked
+    pub fn consistency_score(&self) -> Option<f64> {
+        if self.probes.is_empty() {
+            return None;
+        }
+
+        let consistent = self.probes.iter().filter(|p| p.is_consistent()).count();
+        Some(consistent as f64 / self.probes.len() as f64)
     }
 }
 
@@ -101,3 +275,132 @@ impl Default for Session {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consistency_score_without_probes() {
+        let session = Session::new();
+        assert!(session.consistency_score().is_none());
+    }
+
+    #[test]
+    fn test_consistency_score() {
+        let mut session = Session::new();
+        let a = Id::from("a");
+        let b = Id::from("b");
+
+        session.add_probe(
+            a.clone(),
+            b.clone(),
+            a.clone(),
+            a.clone(),
+            ProbeKind::Repeat,
+        );
+        session.add_probe(
+            a.clone(),
+            b.clone(),
+            a.clone(),
+            b.clone(),
+            ProbeKind::Repeat,
+        );
+        session.add_probe(
+            b.clone(),
+            a.clone(),
+            a.clone(),
+            a.clone(),
+            ProbeKind::Transitive,
+        );
+        session.add_probe(b.clone(), a.clone(), a.clone(), a, ProbeKind::Transitive);
+
+        assert_eq!(session.consistency_score(), Some(0.75));
+    }
+
+    #[test]
+    fn test_position_bias_counts_probes() {
+        let mut session = Session::new();
+        let a = Id::from("a");
+        let b = Id::from("b");
+        session
+            .add_comparison(a.clone(), b.clone(), a.clone())
+            .shown_first = Some(a.clone());
+        session
+            .add_probe(
+                a.clone(),
+                b.clone(),
+                a.clone(),
+                a.clone(),
+                ProbeKind::Repeat,
+            )
+            .shown_first = Some(b.clone());
+        // Older probes without a recorded order are skipped
+        session.add_probe(a.clone(), b, a.clone(), a.clone(), ProbeKind::Repeat);
+
+        let bias = session.position_bias();
+        assert_eq!((bias.first, bias.second), (1, 1));
+
+        session.clear_comparisons();
+        assert_eq!(session.position_bias().total(), 2);
+    }
+
+    #[test]
+    fn test_clear_comparisons_keeps_summaries() {
+        let mut session = Session::new();
+        let a = Id::from("a");
+        let b = Id::from("b");
+        let comparison = session.add_comparison(a.clone(), b, a.clone());
+        comparison.shown_first = Some(a);
+        comparison.latency_ms = Some(1500);
+
+        session.clear_comparisons();
+        assert!(session.comparisons.is_empty());
+        assert_eq!(session.position_bias().first, 1);
+        assert_eq!(session.latency_stats().median_ms, Some(1500));
+        assert_eq!(session.item_records()[&Id::from("a")].wins, 1);
+
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.position_bias().total(), 1);
+        assert_eq!(restored.latency_stats().count, 1);
+    }
+
+    #[test]
+    fn test_cycles_include_probes() {
+        let mut session = Session::new();
+        let (a, b, c) = (Id::from("a"), Id::from("b"), Id::from("c"));
+        session.add_comparison(a.clone(), b.clone(), a.clone());
+        session.add_comparison(b.clone(), c.clone(), b.clone());
+        assert!(session.cycles().is_empty());
+
+        // c beating a closes the loop, though it was only asked as a probe
+        session.add_probe(a.clone(), c.clone(), a.clone(), c, ProbeKind::Transitive);
+        assert_eq!(session.cycles().len(), 1);
+
+        session.clear_comparisons();
+        assert_eq!(session.cycles().len(), 1);
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.cycles(), session.cycles());
+    }
+
+    #[test]
+    fn test_probes_roundtrip() {
+        let mut session = Session::new();
+        let json = serde_json::to_value(&session).unwrap();
+        assert!(json.get("probes").is_none());
+
+        session.add_probe(
+            Id::from("a"),
+            Id::from("b"),
+            Id::from("a"),
+            Id::from("a"),
+            ProbeKind::Transitive,
+        );
+        let json = serde_json::to_string(&session).unwrap();
+        let restored: Session = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.probes.len(), 1);
+        assert_eq!(restored.probes[0].kind, ProbeKind::Transitive);
+    }
+}
@@ -0,0 +1,294 @@
+//! Running strategies against a simulated ranker
+//!
+//! A simulated ranker knows the true order of the items and answers every
+//! pair from it, getting some answers wrong according to a noise model.
+//! Repeating this over many runs shows how many comparisons a strategy
+//! needs and how close its result gets to the truth.
+
+use crate::correlation;
+use crate::seed::{self, Stream};
+use crate::strategy::{self, StrategyConfig};
+use crate::{Error, Id, Item, Result};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the simulated ranker gets answers wrong
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum Noise {
+    /// Every answer is right
+    #[default]
+    None,
+    /// Any answer is wrong with this probability
+    Flip { rate: f64 },
+    /// Items close in the true order are mixed up more often: an answer
+    /// about items `gap` places apart is wrong with probability
+    /// `0.5 * exp(-gap / spread)`
+    Close { spread: f64 },
+}
+
+impl Noise {
+    /// Probability of a wrong answer about items `gap` places apart
+    fn error_rate(&self, gap: usize) -> f64 {
+        match *self {
+            Noise::None => 0.0,
+            Noise::Flip { rate } => rate,
+            Noise::Close { spread } => 0.5 * (-(gap as f64) / spread).exp(),
+        }
+    }
+}
+
+impl FromStr for Noise {
+    type Err = Error;
+
+    /// Parse "none", "flip:RATE" or "close:SPREAD"
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::Other(format!(
+                "Invalid noise '{}'. Use none, flip:RATE (0 to 1) or close:SPREAD (above 0)",
+                s
+            ))
+        };
+        let (model, value) = s.split_once(':').unwrap_or((s, ""));
+        let value = || value.trim().parse::<f64>().map_err(|_| invalid());
+
+        match model.trim().to_lowercase().as_str() {
+            "none" => Ok(Noise::None),
+            "flip" => match value()? {
+                rate if (0.0..=1.0).contains(&rate) => Ok(Noise::Flip { rate }),
+                _ => Err(invalid()),
+            },
+            "close" => match value()? {
+                spread if spread.is_finite() && spread > 0.0 => Ok(Noise::Close { spread }),
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for Noise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Noise::None => write!(f, "none"),
+            Noise::Flip { rate } => write!(f, "flip:{}", rate),
+            Noise::Close { spread } => write!(f, "close:{}", spread),
+        }
+    }
+}
+
+/// A simulated ranker with a known true order
+pub struct Oracle {
+    position: HashMap<Id, usize>,
+    noise: Noise,
+    rng: StdRng,
+}
+
+impl Oracle {
+    /// `truth` lists the items best first
+    pub fn new(truth: &[Id], noise: Noise, rng: StdRng) -> Self {
+        Self {
+            position: truth
+                .iter()
+                .enumerate()
+                .map(|(i, id)| (id.clone(), i))
+                .collect(),
+            noise,
+            rng,
+        }
+    }
+
+    /// The item the ranker prefers
+    pub fn answer<'a>(&mut self, a: &'a Id, b: &'a Id) -> &'a Id {
+        let (pa, pb) = (self.position[a], self.position[b]);
+        let (better, worse) = if pa < pb { (a, b) } else { (b, a) };
+        if self.rng.random_bool(self.noise.error_rate(pa.abs_diff(pb))) {
+            worse
+        } else {
+            better
+        }
+    }
+}
+
+/// What to simulate
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub strategy: StrategyConfig,
+    pub items: usize,
+    pub runs: usize,
+    pub noise: Noise,
+    pub seed: u64,
+}
+
+/// Outcome of one simulated ranking
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Run {
+    pub comparisons: usize,
+    /// Kendall's tau between the result and the true order
+    pub kendall_tau: f64,
+}
+
+/// Mean and spread of a measure over all runs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Stats {
+    pub mean: f64,
+    /// Population variance
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Stats {
+    fn from_values(values: &[f64]) -> Self {
+        let n = values.len().max(1) as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        Self {
+            mean,
+            variance: values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// Results of all runs of a simulation
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub strategy: &'static str,
+    pub runs: Vec<Run>,
+    pub comparisons: Stats,
+    pub kendall_tau: Stats,
+}
+
+impl Simulation {
+    /// Run the simulation; the same settings always give the same report
+    pub fn run(&self) -> Result<Report> {
+        if self.items < 2 {
+            return Err(Error::Other("Simulate at least 2 items".to_string()));
+        }
+
+        let runs = (0..self.runs)
+            .map(|i| self.run_once(i as u64))
+            .collect::<Result<Vec<Run>>>()?;
+        let comparisons: Vec<f64> = runs.iter().map(|r| r.comparisons as f64).collect();
+        let taus: Vec<f64> = runs.iter().map(|r| r.kendall_tau).collect();
+
+        Ok(Report {
+            strategy: self.strategy.name(),
+            comparisons: Stats::from_values(&comparisons),
+            kendall_tau: Stats::from_values(&taus),
+            runs,
+        })
+    }
+
+    fn run_once(&self, index: u64) -> Result<Run> {
+        let mut rng = seed::rng(self.seed, Stream::Simulation, index);
+        let run_seed: u64 = rng.random();
+
+        let items: Vec<Item> = (0..self.items)
+            .map(|i| {
+                let id = Id::from(format!("i{:03}", i));
+                Item::with_id(id, format!("Item {}", i + 1), chrono::Utc::now())
+            })
+            .collect();
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let by_id: HashMap<&Id, &Item> = items.iter().map(|item| (&item.id, item)).collect();
+
+        // The true order is shuffled, so input order gives nothing away
+        let mut truth = ids.clone();
+        truth.shuffle(&mut rng);
+        let mut oracle = Oracle::new(&truth, self.noise, StdRng::seed_from_u64(run_seed));
+
+        let mut strategy = strategy::create_configured(&self.strategy, ids);
+        strategy.seed(run_seed);
+
+        // Even asking every pair twice is far more than any strategy needs
+        let limit = self.items * self.items;
+        let mut comparisons = 0;
+        while let Some((a, b)) = strategy.next_comparison() {
+            if comparisons >= limit {
+                return Err(Error::Other(format!(
+                    "{} did not finish within {} comparisons",
+                    self.strategy.name(),
+                    limit
+                )));
+            }
+            let winner = oracle.answer(&a, &b).clone();
+            strategy.compare(by_id[&a], by_id[&b], &winner)?;
+            comparisons += 1;
+        }
+
+        let order = strategy.finalize()?.order.unwrap_or_default();
+        Ok(Run {
+            comparisons,
+            kendall_tau: correlation::kendall_tau(&order, &truth),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "merge", feature = "quicksort", feature = "elo"))]
+    fn simulation(strategy: &str, noise: Noise) -> Simulation {
+        Simulation {
+            strategy: StrategyConfig::default_for(strategy).unwrap(),
+            items: 12,
+            runs: 20,
+            noise,
+            seed: 3,
+        }
+    }
+
+    #[test]
+    #[cfg(any(feature = "merge", feature = "quicksort", feature = "elo"))]
+    fn test_noiseless_sorts_exactly() {
+        for name in strategy::available() {
+            let report = simulation(name, Noise::None).run().unwrap();
+            assert_eq!(report.runs.len(), 20);
+            if name != "elo" {
+                assert_eq!(report.kendall_tau.min, 1.0, "{}", name);
+            }
+            // n log2 n for 12 items rounds up to 44, and there are 66 pairs
+            assert!(report.comparisons.mean <= 44.0, "{}", name);
+            assert!(report.comparisons.max <= 66.0, "{}", name);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "merge")]
+    fn test_noise_lowers_agreement_reproducibly() {
+        let noisy = simulation("merge", Noise::Flip { rate: 0.2 });
+        let report = noisy.run().unwrap();
+        assert!(report.kendall_tau.mean < 1.0);
+        assert!(report.kendall_tau.variance > 0.0);
+        assert_eq!(noisy.run().unwrap().runs, report.runs);
+    }
+
+    #[test]
+    fn test_parse_noise() {
+        assert_eq!("none".parse::<Noise>().unwrap(), Noise::None);
+        assert_eq!(
+            "flip:0.1".parse::<Noise>().unwrap(),
+            Noise::Flip { rate: 0.1 }
+        );
+        assert_eq!(
+            "close:2".parse::<Noise>().unwrap(),
+            Noise::Close { spread: 2.0 }
+        );
+        assert!("flip:2".parse::<Noise>().is_err());
+        assert!("close:0".parse::<Noise>().is_err());
+        assert!("loud".parse::<Noise>().is_err());
+        assert_eq!(Noise::Flip { rate: 0.1 }.to_string(), "flip:0.1");
+    }
+}
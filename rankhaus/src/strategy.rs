@@ -1,14 +1,27 @@
-use crate::{Id, Item, Result};
+use crate::{Error, Id, Item, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Result of a ranking operation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RankResult {
     /// Ordered list of item IDs (best to worst)
     pub order: Option<Vec<Id>>,
     /// Rating scores for each item
     pub ratings: Option<HashMap<Id, f64>>,
+    /// Items grouped into tiers, best tier first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tiers: Option<Vec<crate::tier::Tier>>,
+    /// Direct scores given to each item in score mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scores: Option<HashMap<Id, u8>>,
+    /// Item groups, best group first, when ranked by group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<crate::group::ItemGroup>>,
+    /// How settled an order is that was finished early, from 0 to 1;
+    /// `None` when the ranking ran to completion
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
 }
 
 /// Trait for ranking strategies
@@ -20,9 +33,47 @@ pub trait RankStrategy: Send + Sync {
     /// Returns the ID of the preferred item
     fn compare(&mut self, a: &Item, b: &Item, winner_id: &Id) -> Result<()>;
 
+    /// Record a comparison answered outside this strategy's own sequence,
+    /// e.g. when replaying a session or switching from another strategy.
+    /// The pair is treated as already answered and will not be asked again.
+    /// Pairs involving unknown items are ignored.
+    fn record_prior(&mut self, a: &Id, b: &Id, winner_id: &Id) -> Result<()>;
+
+    /// Record a preference known without asking, e.g. from constraints or
+    /// tiers. The pair will not be asked, and unlike `record_prior` it does
+    /// not count towards the comparisons a strategy plans to make; a later
+    /// answer on the pair doesn't either. Defaults to `record_prior`.
+    fn record_known(&mut self, a: &Id, b: &Id, winner_id: &Id) -> Result<()> {
+        self.record_prior(a, b, winner_id)
+    }
+
+    /// Record an answer given with less certainty, with `weight` in (0, 1].
+    /// Rating strategies scale the answer's effect by the weight; the
+    /// default treats it like any other answer via `record_prior`.
+    fn record_weighted(&mut self, a: &Id, b: &Id, winner_id: &Id, _weight: f64) -> Result<()> {
+        self.record_prior(a, b, winner_id)
+    }
+
+    /// Draw the strategy's random choices, such as quicksort pivots, from
+    /// `seed`. Call before recording any comparisons. Strategies without
+    /// random choices ignore it.
+    fn seed(&mut self, _seed: u64) {}
+
+    /// Use an earlier ordering of the items (best to worst) as a hint, so a
+    /// re-rank mostly confirms it. Call before recording any comparisons.
+    /// Items missing from the hint are treated as ranked last.
+    fn warm_start(&mut self, _order: &[Id]) {}
+
     /// Complete the ranking and return results
     fn finalize(&mut self) -> Result<RankResult>;
 
+    /// Best estimate of the result so far, for a ranking stopped before it
+    /// completes, with `confidence` set. A complete ranking returns the same
+    /// as `finalize`.
+    fn finalize_best_effort(&mut self) -> Result<RankResult> {
+        self.finalize()
+    }
+
     /// Serialize the current state for persistence
     fn serialize_state(&self) -> Result<serde_json::Value>;
 
@@ -36,16 +87,262 @@ pub trait RankStrategy: Send + Sync {
     fn is_complete(&self) -> bool;
 }
 
+/// Names of the strategies compiled into this build
+#[allow(clippy::vec_init_then_push)]
+pub fn available() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut strategies = Vec::new();
+
+    #[cfg(feature = "merge")]
+    strategies.push("merge");
+
+    #[cfg(feature = "quicksort")]
+    strategies.push("quicksort");
+
+    #[cfg(feature = "elo")]
+    strategies.push("elo");
+
+    strategies
+}
+
+/// Create a strategy by name for the given items, with default options
+pub fn create(name: &str, items: Vec<Id>) -> Result<Box<dyn RankStrategy>> {
+    Ok(create_configured(
+        &StrategyConfig::default_for(name)?,
+        items,
+    ))
+}
+
+/// Create the strategy a configuration is for, with its options
+#[cfg_attr(
+    not(any(feature = "merge", feature = "quicksort", feature = "elo")),
+    allow(unused_variables)
+)]
+pub fn create_configured(config: &StrategyConfig, items: Vec<Id>) -> Box<dyn RankStrategy> {
+    match config {
+        #[cfg(feature = "merge")]
+        StrategyConfig::Merge => Box::new(merge::MergeStrategy::new(items)),
+        #[cfg(feature = "quicksort")]
+        StrategyConfig::Quicksort(config) => {
+            Box::new(quicksort::QuickSortStrategy::with_config(items, *config))
+        }
+        #[cfg(feature = "elo")]
+        StrategyConfig::Elo(config) => Box::new(elo::EloStrategy::with_config(items, *config)),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("no strategies are compiled in"),
+    }
+}
+
+/// Version of a strategy's algorithm, bumped whenever the same answers
+/// could lead it to ask different questions or give a different result
+pub fn version(name: &str) -> Option<u32> {
+    match name {
+        #[cfg(feature = "merge")]
+        "merge" => Some(merge::VERSION),
+        #[cfg(feature = "quicksort")]
+        "quicksort" => Some(quicksort::VERSION),
+        #[cfg(feature = "elo")]
+        "elo" => Some(elo::VERSION),
+        _ => None,
+    }
+}
+
+/// Whether a strategy scales answers recorded with `record_weighted`
+/// rather than treating them like any other answer
+pub fn uses_weights(name: &str) -> bool {
+    match name {
+        #[cfg(feature = "elo")]
+        "elo" => true,
+        _ => false,
+    }
+}
+
+/// A strategy together with its options
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum StrategyConfig {
+    #[cfg(feature = "merge")]
+    Merge,
+    #[cfg(feature = "quicksort")]
+    Quicksort(quicksort::QuickSortConfig),
+    #[cfg(feature = "elo")]
+    Elo(elo::EloConfig),
+}
+
+impl StrategyConfig {
+    /// Default options for the named strategy
+    pub fn default_for(name: &str) -> Result<Self> {
+        match name {
+            #[cfg(feature = "merge")]
+            "merge" => Ok(Self::Merge),
+            #[cfg(feature = "quicksort")]
+            "quicksort" => Ok(Self::Quicksort(Default::default())),
+            #[cfg(feature = "elo")]
+            "elo" => Ok(Self::Elo(Default::default())),
+            _ => Err(Error::StrategyNotFound(name.to_string())),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "merge")]
+            Self::Merge => "merge",
+            #[cfg(feature = "quicksort")]
+            Self::Quicksort(_) => "quicksort",
+            #[cfg(feature = "elo")]
+            Self::Elo(_) => "elo",
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("no strategies are compiled in"),
+        }
+    }
+
+    /// Set an option from its text value, e.g. `("k_factor", "16")`
+    #[cfg_attr(
+        not(any(feature = "quicksort", feature = "elo")),
+        allow(unused_variables)
+    )]
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match self {
+            #[cfg(feature = "merge")]
+            Self::Merge => Err(unknown_option("merge", key, &[])),
+            #[cfg(feature = "quicksort")]
+            Self::Quicksort(config) => config.set(key, value),
+            #[cfg(feature = "elo")]
+            Self::Elo(config) => config.set(key, value),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("no strategies are compiled in"),
+        }
+    }
+
+    /// Current value of every option
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        match self {
+            #[cfg(feature = "merge")]
+            Self::Merge => Vec::new(),
+            #[cfg(feature = "quicksort")]
+            Self::Quicksort(config) => config.options(),
+            #[cfg(feature = "elo")]
+            Self::Elo(config) => config.options(),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!("no strategies are compiled in"),
+        }
+    }
+
+    /// Options as "key=value, ..." or an empty string if there are none
+    pub fn describe(&self) -> String {
+        self.options()
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(any(feature = "merge", feature = "quicksort", feature = "elo"))]
+fn unknown_option(strategy: &str, key: &str, options: &[&str]) -> Error {
+    if options.is_empty() {
+        Error::Other(format!("Strategy {} has no options", strategy))
+    } else {
+        Error::Other(format!(
+            "Unknown option '{}' for {} (options: {})",
+            key,
+            strategy,
+            options.join(", ")
+        ))
+    }
+}
+
+#[cfg(any(feature = "merge", feature = "quicksort"))]
+/// Answers cached by the sorting strategies: the pair's IDs, mapped to the
+/// winner's ID
+type AnswerCache = HashMap<(String, String), String>;
+
+/// Finish a copy of a sorting strategy by guessing its unanswered pairs
+///
+/// Each guess favors the item with more net wins so far, keeping the first
+/// of the pair on a tie. The confidence is the share of neighbouring items
+/// whose order follows from real answers.
+#[cfg(any(feature = "merge", feature = "quicksort"))]
+fn complete_by_guessing<S: RankStrategy>(
+    mut guess: S,
+    answers: &AnswerCache,
+) -> Result<RankResult> {
+    let mut net_wins: HashMap<&str, i64> = HashMap::new();
+    for (winner, loser) in answer_pairs(answers) {
+        *net_wins.entry(winner).or_default() += 1;
+        *net_wins.entry(loser).or_default() -= 1;
+    }
+    let wins = |id: &Id| net_wins.get(id.as_str()).copied().unwrap_or(0);
+
+    while let Some((a, b)) = guess.next_comparison() {
+        let winner = if wins(&b) > wins(&a) {
+            b.clone()
+        } else {
+            a.clone()
+        };
+        guess.record_prior(&a, &b, &winner)?;
+    }
+
+    let mut result = guess.finalize()?;
+    result.confidence = result
+        .order
+        .as_ref()
+        .map(|order| settled_share(order, answers));
+    Ok(result)
+}
+
+#[cfg(any(feature = "merge", feature = "quicksort"))]
+/// (winner, loser) for each cached answer
+fn answer_pairs(answers: &AnswerCache) -> impl Iterator<Item = (&str, &str)> {
+    answers.iter().map(|((x, y), winner)| {
+        let loser = if winner == x { y } else { x };
+        (winner.as_str(), loser.as_str())
+    })
+}
+
+#[cfg(any(feature = "merge", feature = "quicksort"))]
+/// Share of neighbouring items in `order` whose order follows from the
+/// answers, directly or through a chain of answers
+fn settled_share(order: &[Id], answers: &AnswerCache) -> f64 {
+    if order.len() < 2 {
+        return 1.0;
+    }
+
+    let mut beaten: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (winner, loser) in answer_pairs(answers) {
+        beaten.entry(winner).or_default().push(loser);
+    }
+    let reaches = |from: &str, to: &str| {
+        let mut seen = std::collections::HashSet::new();
+        let mut stack = vec![from];
+        while let Some(id) = stack.pop() {
+            if id == to {
+                return true;
+            }
+            if seen.insert(id) {
+                stack.extend(beaten.get(id).into_iter().flatten());
+            }
+        }
+        false
+    };
+
+    let settled = order
+        .windows(2)
+        .filter(|pair| reaches(pair[0].as_str(), pair[1].as_str()))
+        .count();
+    settled as f64 / (order.len() - 1) as f64
+}
+
 #[cfg(feature = "merge")]
 pub mod merge;
 
 #[cfg(feature = "quicksort")]
 pub mod quicksort;
 
+#[cfg(feature = "elo")]
+pub mod elo;
+
 // TODO: Implement additional strategies
-// #[cfg(feature = "elo")]
-// pub mod elo;
-//
 // #[cfg(feature = "tournament")]
 // pub mod tournament;
 //
@@ -57,3 +354,151 @@ pub mod quicksort;
 //
 // #[cfg(feature = "btm")]
 // pub mod btm;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_available_strategies() {
+        let items: Vec<Id> = vec!["a".into(), "b".into(), "c".into()];
+        for name in available() {
+            let strategy = create(name, items.clone()).unwrap();
+            assert_eq!(strategy.name(), name);
+            assert!(strategy.next_comparison().is_some());
+        }
+    }
+
+    #[test]
+    fn test_finalize_best_effort() {
+        let items: Vec<Id> = (0..6).map(|i| Id::from(format!("i{}", i))).collect();
+        for name in available() {
+            let mut strategy = create(name, items.clone()).unwrap();
+            // Lower numbers win
+            for _ in 0..3 {
+                let (a, b) = strategy.next_comparison().unwrap();
+                let winner = a.clone().min(b.clone());
+                strategy.record_prior(&a, &b, &winner).unwrap();
+            }
+            assert!(strategy.finalize().is_err());
+
+            let result = strategy.finalize_best_effort().unwrap();
+            let mut order = result.order.unwrap();
+            order.sort();
+            assert_eq!(order, items, "{} should order every item", name);
+            let confidence = result.confidence.unwrap();
+            assert!((0.0..1.0).contains(&confidence), "{}: {}", name, confidence);
+        }
+    }
+
+    #[test]
+    fn test_builtin_strategies_conform() {
+        use crate::testkit::Conformance;
+
+        for name in available() {
+            let conformance = Conformance::new(|items| {
+                let mut strategy = create(name, items).unwrap();
+                strategy.seed(7);
+                strategy
+            });
+            // Elo stops at its comparison target, so only approximates the order
+            if name == "elo" {
+                conformance.approximate(0.5).assert_conforms();
+            } else {
+                conformance.assert_conforms();
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_group_orders_leave_cross_group_pairs() {
+        use crate::group::ItemGroup;
+
+        // Two groups already ranked on their own; lower numbers win
+        let ids: Vec<Id> = (0..8).map(|i| Id::from(format!("i{}", i))).collect();
+        let groups = [
+            ItemGroup {
+                name: "even".to_string(),
+                items: ids.iter().step_by(2).cloned().collect(),
+            },
+            ItemGroup {
+                name: "odd".to_string(),
+                items: ids.iter().skip(1).step_by(2).cloned().collect(),
+            },
+        ];
+        let group_of = |id: &Id| groups.iter().position(|g| g.items.contains(id));
+
+        for name in available() {
+            let mut strategy = create(name, ids.clone()).unwrap();
+            for (winner, loser) in groups.iter().flat_map(ItemGroup::implied_pairs) {
+                strategy.record_known(&winner, &loser, &winner).unwrap();
+            }
+
+            let mut asked = 0;
+            while let Some((a, b)) = strategy.next_comparison() {
+                assert_ne!(group_of(&a), group_of(&b), "{} asked within a group", name);
+                let winner = a.clone().min(b.clone());
+                strategy.record_prior(&a, &b, &winner).unwrap();
+                asked += 1;
+            }
+            assert!(asked > 0, "{} never merged the groups", name);
+        }
+    }
+
+    #[cfg(any(feature = "merge", feature = "quicksort"))]
+    #[test]
+    fn test_settled_share() {
+        let answer = |winner: &str, loser: &str| {
+            ((winner.to_string(), loser.to_string()), winner.to_string())
+        };
+        // a beat b and b beat c; c and d were never compared
+        let answers: AnswerCache = [answer("a", "b"), answer("b", "c")].into_iter().collect();
+        let order: Vec<Id> = ["a", "b", "c", "d"].into_iter().map(Id::from).collect();
+        assert!((settled_share(&order, &answers) - 2.0 / 3.0).abs() < 1e-9);
+
+        // a before c follows through b
+        let order: Vec<Id> = ["a", "c"].into_iter().map(Id::from).collect();
+        assert_eq!(settled_share(&order, &answers), 1.0);
+    }
+
+    #[cfg(all(feature = "merge", feature = "elo"))]
+    #[test]
+    fn test_strategy_config() {
+        let mut config = StrategyConfig::default_for("elo").unwrap();
+        config.set("k_factor", "16").unwrap();
+        assert_eq!(config.describe(), "k_factor=16, effort=1");
+        assert!(config.set("k_factor", "-1").is_err());
+        assert!(config.set("pivot", "middle").is_err());
+
+        // Saved with the strategy's name, so it loads back as the same type
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["strategy"], "elo");
+        assert_eq!(
+            serde_json::from_value::<StrategyConfig>(json).unwrap(),
+            config
+        );
+
+        let strategy = create_configured(&config, vec!["a".into(), "b".into()]);
+        assert_eq!(strategy.name(), "elo");
+
+        let mut merge = StrategyConfig::default_for("merge").unwrap();
+        assert!(merge.set("k_factor", "16").is_err());
+        assert_eq!(merge.describe(), "");
+    }
+
+    #[test]
+    fn test_uses_weights() {
+        assert_eq!(uses_weights("elo"), cfg!(feature = "elo"));
+        assert!(!uses_weights("merge"));
+        assert!(!uses_weights("quicksort"));
+        assert!(!uses_weights("nonexistent"));
+    }
+
+    #[test]
+    fn test_create_unknown_strategy() {
+        assert!(matches!(
+            create("nonexistent", vec![]),
+            Err(Error::StrategyNotFound(_))
+        ));
+    }
+}
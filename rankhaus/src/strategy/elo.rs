@@ -0,0 +1,612 @@
+use crate::{
+    strategy::{RankResult, RankStrategy},
+    Id, Item, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
+
+/// Rating every item starts with
+const INITIAL_RATING: f64 = 1500.0;
+
+/// Version of the rating and pairing rules, recorded with each ranking
+pub const VERSION: u32 = 2;
+
+/// Default maximum rating change from a single comparison
+const K_FACTOR: f64 = 32.0;
+
+/// Rating gap between the first and last item of a warm start hint
+const WARM_START_SPREAD: f64 = 400.0;
+
+/// Options for the Elo strategy
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EloConfig {
+    /// Maximum rating change from a single comparison
+    pub k_factor: f64,
+    /// Multiplier on the n log2(n) comparisons made before completing
+    pub effort: f64,
+}
+
+impl Default for EloConfig {
+    fn default() -> Self {
+        Self {
+            k_factor: K_FACTOR,
+            effort: 1.0,
+        }
+    }
+}
+
+impl EloConfig {
+    pub const OPTIONS: &'static [&'static str] = &["k_factor", "effort"];
+
+    /// Set an option from its text value
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let number = || -> Result<f64> {
+            value
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite() && *v > 0.0)
+                .ok_or_else(|| crate::Error::Other(format!("{} must be a positive number", key)))
+        };
+        match key {
+            "k_factor" => self.k_factor = number()?,
+            "effort" => self.effort = number()?,
+            _ => return Err(super::unknown_option("elo", key, Self::OPTIONS)),
+        }
+        Ok(())
+    }
+
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("k_factor", self.k_factor.to_string()),
+            ("effort", self.effort.to_string()),
+        ]
+    }
+}
+
+/// Elo rating based ranking strategy
+///
+/// Each answer moves the winner's rating up and the loser's down. Pairs are
+/// chosen between items with few games and close ratings, and the ranking
+/// completes after about n log2(n) comparisons.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EloStrategy {
+    items: Vec<Id>,
+    #[serde(default)]
+    config: EloConfig,
+    state: EloState,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EloState {
+    /// Current rating of each item
+    ratings: HashMap<Id, f64>,
+    /// Number of comparisons each item took part in
+    games: HashMap<Id, usize>,
+    /// Pairs already compared, as (smaller, larger) IDs
+    played: BTreeSet<(Id, Id)>,
+    /// Pairs whose order was known without asking, as (smaller, larger) IDs
+    #[serde(default)]
+    known: BTreeSet<(Id, Id)>,
+    /// Number of comparisons applied so far
+    comparisons: usize,
+    /// Number of comparisons after which the ranking is complete
+    target: usize,
+}
+
+impl EloStrategy {
+    pub fn new(items: Vec<Id>) -> Self {
+        Self::with_config(items, EloConfig::default())
+    }
+
+    pub fn with_config(items: Vec<Id>, config: EloConfig) -> Self {
+        let n = items.len();
+        let all_pairs = n * n.saturating_sub(1) / 2;
+        let target = if n < 2 {
+            0
+        } else {
+            ((n as f64) * (n as f64).log2() * config.effort)
+                .ceil()
+                .max(1.0) as usize
+        };
+
+        Self {
+            state: EloState {
+                ratings: items
+                    .iter()
+                    .map(|id| (id.clone(), INITIAL_RATING))
+                    .collect(),
+                games: items.iter().map(|id| (id.clone(), 0)).collect(),
+                played: BTreeSet::new(),
+                known: BTreeSet::new(),
+                comparisons: 0,
+                target: target.min(all_pairs),
+            },
+            items,
+            config,
+        }
+    }
+
+    fn pair_key(a: &Id, b: &Id) -> (Id, Id) {
+        if a < b {
+            (a.clone(), b.clone())
+        } else {
+            (b.clone(), a.clone())
+        }
+    }
+
+    fn rating(&self, id: &Id) -> f64 {
+        self.state
+            .ratings
+            .get(id)
+            .copied()
+            .unwrap_or(INITIAL_RATING)
+    }
+
+    fn games(&self, id: &Id) -> usize {
+        self.state.games.get(id).copied().unwrap_or(0)
+    }
+
+    /// Apply a single answer to the ratings, scaling the change by `weight`
+    ///
+    /// Answers on pairs known in advance move the ratings but don't count as
+    /// games or towards the comparison target.
+    fn apply(&mut self, a: &Id, b: &Id, winner_id: &Id, weight: f64) {
+        let rating_a = self.rating(a);
+        let rating_b = self.rating(b);
+
+        let expected_a = 1.0 / (1.0 + 10f64.powf((rating_b - rating_a) / 400.0));
+        let score_a = if winner_id == a { 1.0 } else { 0.0 };
+        let delta = self.config.k_factor * weight * (score_a - expected_a);
+
+        self.state.ratings.insert(a.clone(), rating_a + delta);
+        self.state.ratings.insert(b.clone(), rating_b - delta);
+
+        let key = Self::pair_key(a, b);
+        if self.state.known.contains(&key) {
+            return;
+        }
+        *self.state.games.entry(a.clone()).or_insert(0) += 1;
+        *self.state.games.entry(b.clone()).or_insert(0) += 1;
+        self.state.played.insert(key);
+        self.state.comparisons += 1;
+    }
+
+    /// Items ordered by rating, best first
+    fn ordered(&self) -> Vec<Id> {
+        let mut order = self.items.clone();
+        order.sort_by(|a, b| {
+            self.rating(b)
+                .partial_cmp(&self.rating(a))
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.cmp(b))
+        });
+        order
+    }
+}
+
+impl RankStrategy for EloStrategy {
+    fn name(&self) -> &'static str {
+        "elo"
+    }
+
+    fn compare(&mut self, a: &Item, b: &Item, winner_id: &Id) -> Result<()> {
+        self.apply(&a.id, &b.id, winner_id, 1.0);
+        Ok(())
+    }
+
+    fn record_prior(&mut self, a: &Id, b: &Id, winner_id: &Id) -> Result<()> {
+        self.record_weighted(a, b, winner_id, 1.0)
+    }
+
+    fn record_weighted(&mut self, a: &Id, b: &Id, winner_id: &Id, weight: f64) -> Result<()> {
+        if !self.items.contains(a) || !self.items.contains(b) {
+            return Ok(());
+        }
+
+        self.apply(a, b, winner_id, weight.clamp(0.0, 1.0));
+        Ok(())
+    }
+
+    fn record_known(&mut self, a: &Id, b: &Id, winner_id: &Id) -> Result<()> {
+        if !self.items.contains(a) || !self.items.contains(b) || a == b {
+            return Ok(());
+        }
+
+        let key = Self::pair_key(a, b);
+        self.state.played.insert(key.clone());
+        self.state.known.insert(key);
+        self.apply(a, b, winner_id, 1.0);
+        Ok(())
+    }
+
+    fn warm_start(&mut self, order: &[Id]) {
+        // Spread initial ratings evenly by prior position, unknown items last
+        let known: Vec<&Id> = order.iter().filter(|id| self.items.contains(id)).collect();
+        let step = WARM_START_SPREAD / self.items.len().saturating_sub(1).max(1) as f64;
+        let top = INITIAL_RATING + WARM_START_SPREAD / 2.0;
+
+        for id in &self.items {
+            let position = known
+                .iter()
+                .position(|k| *k == id)
+                .unwrap_or(self.items.len() - 1);
+            self.state
+                .ratings
+                .insert(id.clone(), top - step * position as f64);
+        }
+    }
+
+    fn finalize(&mut self) -> Result<RankResult> {
+        if !self.is_complete() {
+            return Err(crate::Error::Other("Ranking not complete".to_string()));
+        }
+
+        Ok(RankResult {
+            order: Some(self.ordered()),
+            ratings: Some(self.state.ratings.clone()),
+            tiers: None,
+            scores: None,
+            groups: None,
+            confidence: None,
+        })
+    }
+
+    /// Ratings are a usable order at any point; confidence is the share of
+    /// the planned comparisons made
+    fn finalize_best_effort(&mut self) -> Result<RankResult> {
+        if self.is_complete() {
+            return self.finalize();
+        }
+
+        Ok(RankResult {
+            order: Some(self.ordered()),
+            ratings: Some(self.state.ratings.clone()),
+            confidence: Some(self.state.comparisons as f64 / self.state.target as f64),
+            ..Default::default()
+        })
+    }
+
+    fn serialize_state(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(&self.state)?)
+    }
+
+    fn deserialize_state(&mut self, state: serde_json::Value) -> Result<()> {
+        self.state = serde_json::from_value(state)?;
+        Ok(())
+    }
+
+    fn next_comparison(&self) -> Option<(Id, Id)> {
+        if self.is_complete() {
+            return None;
+        }
+
+        // Give items with the fewest games a turn first
+        let mut candidates: Vec<&Id> = self.items.iter().collect();
+        candidates.sort_by(|a, b| self.games(a).cmp(&self.games(b)).then_with(|| a.cmp(b)));
+
+        for first in candidates {
+            // Pair with the closest rated item it hasn't played yet
+            let partner = self
+                .items
+                .iter()
+                .filter(|other| {
+                    *other != first && !self.state.played.contains(&Self::pair_key(first, other))
+                })
+                .min_by(|x, y| {
+                    let dx = (self.rating(x) - self.rating(first)).abs();
+                    let dy = (self.rating(y) - self.rating(first)).abs();
+                    dx.partial_cmp(&dy)
+                        .unwrap_or(Ordering::Equal)
+                        .then_with(|| self.games(x).cmp(&self.games(y)))
+                        .then_with(|| x.cmp(y))
+                });
+
+            if let Some(partner) = partner {
+                return Some((first.clone(), partner.clone()));
+            }
+        }
+
+        None
+    }
+
+    fn is_complete(&self) -> bool {
+        let n = self.items.len();
+        self.state.comparisons >= self.state.target
+            || self.state.played.len() >= n * n.saturating_sub(1) / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_items(count: usize) -> Vec<Item> {
+        (0..count)
+            .map(|i| {
+                Item::with_id(
+                    Id::from(format!("i{}", i)),
+                    format!("Item {}", i),
+                    chrono::Utc::now(),
+                )
+            })
+            .collect()
+    }
+
+    fn run_to_completion(strategy: &mut EloStrategy, items: &[Item]) -> usize {
+        let mut count = 0;
+        while let Some((a, b)) = strategy.next_comparison() {
+            // Always prefer lower index (simulates consistent preference)
+            let winner = if a.as_str() < b.as_str() { &a } else { &b };
+            let item_a = items.iter().find(|i| i.id == a).unwrap();
+            let item_b = items.iter().find(|i| i.id == b).unwrap();
+            strategy.compare(item_a, item_b, winner).unwrap();
+
+            count += 1;
+            assert!(count <= 100, "Too many comparisons");
+        }
+        count
+    }
+
+    #[test]
+    fn test_elo_strategy_creation() {
+        let items = create_test_items(5);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let strategy = EloStrategy::new(ids);
+
+        assert_eq!(strategy.name(), "elo");
+        assert!(!strategy.is_complete());
+    }
+
+    #[test]
+    fn test_empty_and_single_item() {
+        let mut strategy = EloStrategy::new(vec![]);
+        assert!(strategy.is_complete());
+        assert!(strategy.finalize().is_ok());
+
+        let mut strategy = EloStrategy::new(vec![Id::from("i0")]);
+        assert!(strategy.is_complete());
+        assert!(strategy.next_comparison().is_none());
+        assert_eq!(
+            strategy.finalize().unwrap().order.unwrap(),
+            vec![Id::from("i0")]
+        );
+    }
+
+    #[test]
+    fn test_two_items() {
+        let items = create_test_items(2);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let mut strategy = EloStrategy::new(ids.clone());
+
+        strategy.compare(&items[1], &items[0], &ids[1]).unwrap();
+
+        assert!(strategy.is_complete());
+        let result = strategy.finalize().unwrap();
+        assert_eq!(result.order.unwrap(), vec![ids[1].clone(), ids[0].clone()]);
+
+        let ratings = result.ratings.unwrap();
+        assert!(ratings[&ids[1]] > ratings[&ids[0]]);
+    }
+
+    #[test]
+    fn test_consistent_preference() {
+        let items = create_test_items(8);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let mut strategy = EloStrategy::new(ids.clone());
+
+        let count = run_to_completion(&mut strategy, &items);
+        assert_eq!(count, 24); // 8 * log2(8)
+
+        let order = strategy.finalize().unwrap().order.unwrap();
+        assert_eq!(order.len(), 8);
+        assert_eq!(order[0], ids[0]);
+        assert_eq!(order[7], ids[7]);
+    }
+
+    #[test]
+    fn test_no_duplicate_comparisons() {
+        let items = create_test_items(4);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let mut strategy = EloStrategy::new(ids);
+
+        let mut seen = BTreeSet::new();
+        while let Some((a, b)) = strategy.next_comparison() {
+            assert!(seen.insert(EloStrategy::pair_key(&a, &b)));
+            strategy.record_prior(&a, &b, &a).unwrap();
+        }
+
+        // 4 * log2(4) = 8 exceeds the 6 possible pairs
+        assert_eq!(seen.len(), 6);
+    }
+
+    #[test]
+    fn test_record_prior_ignores_unknown_items() {
+        let items = create_test_items(3);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let mut strategy = EloStrategy::new(ids.clone());
+
+        strategy
+            .record_prior(&ids[0], &Id::from("unknown"), &ids[0])
+            .unwrap();
+        assert_eq!(strategy.state.comparisons, 0);
+
+        strategy.record_prior(&ids[0], &ids[1], &ids[0]).unwrap();
+        assert_eq!(strategy.state.comparisons, 1);
+    }
+
+    #[test]
+    fn test_config() {
+        let ids: Vec<Id> = create_test_items(8).into_iter().map(|i| i.id).collect();
+        let default = EloStrategy::new(ids.clone());
+        let config = EloConfig {
+            k_factor: 16.0,
+            effort: 0.5,
+        };
+        let mut custom = EloStrategy::with_config(ids.clone(), config);
+        assert_eq!(custom.state.target, default.state.target / 2);
+
+        custom.record_prior(&ids[0], &ids[1], &ids[0]).unwrap();
+        assert_eq!(custom.rating(&ids[0]), INITIAL_RATING + 8.0);
+    }
+
+    #[test]
+    fn test_weak_answers_move_ratings_less() {
+        let items = create_test_items(2);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        let mut strong = EloStrategy::new(ids.clone());
+        strong.record_prior(&ids[0], &ids[1], &ids[0]).unwrap();
+        let mut weak = EloStrategy::new(ids.clone());
+        weak.record_weighted(&ids[0], &ids[1], &ids[0], 0.5)
+            .unwrap();
+
+        let gain = |s: &EloStrategy| s.rating(&ids[0]) - INITIAL_RATING;
+        assert!((gain(&weak) - gain(&strong) / 2.0).abs() < 1e-9);
+        assert_eq!(weak.state.comparisons, 1);
+    }
+
+    #[test]
+    fn test_known_pairs_leave_target_for_constraints() {
+        use crate::constraint::{self, Constraint};
+
+        // A chain of six items fixes 15 of the 28 pairs
+        let ids: Vec<Id> = create_test_items(8).into_iter().map(|i| i.id).collect();
+        let constraints: Vec<Constraint> = ids[..6]
+            .windows(2)
+            .map(|pair| Constraint::Above {
+                higher: pair[0].clone(),
+                lower: pair[1].clone(),
+            })
+            .collect();
+
+        let mut strategy = EloStrategy::new(ids.clone());
+        for (winner, loser) in constraint::implied_pairs(&constraints) {
+            strategy.record_known(&winner, &loser, &winner).unwrap();
+        }
+        assert_eq!(strategy.state.comparisons, 0);
+
+        let mut asked = 0;
+        while let Some((a, b)) = strategy.next_comparison() {
+            strategy.record_prior(&a, &b, &a).unwrap();
+            asked += 1;
+        }
+        // Every other pair, since the target of 24 is never reached
+        assert_eq!(asked, 13);
+    }
+
+    #[test]
+    fn test_known_pairs_leave_target_for_tiers() {
+        use crate::tier::{TierList, DEFAULT_LABELS};
+
+        // Three tiers of four items: the 48 pairs across tiers alone would
+        // pass the target of 12 log2(12) = 44
+        let ids: Vec<Id> = create_test_items(12).into_iter().map(|i| i.id).collect();
+        let mut tiers = TierList::new(
+            DEFAULT_LABELS[..3].iter().map(|l| l.to_string()).collect(),
+            true,
+        )
+        .unwrap();
+        for (i, id) in ids.iter().enumerate() {
+            tiers.assign(id.clone(), i / 4).unwrap();
+        }
+
+        let mut strategy = EloStrategy::new(ids.clone());
+        for (winner, loser) in tiers.implied_pairs() {
+            strategy.record_known(&winner, &loser, &winner).unwrap();
+        }
+        assert_eq!(strategy.state.comparisons, 0);
+        assert!(!strategy.is_complete());
+
+        let tier_of = |id: &Id| ids.iter().position(|x| x == id).unwrap() / 4;
+        let mut asked = 0;
+        while let Some((a, b)) = strategy.next_comparison() {
+            assert_eq!(
+                tier_of(&a),
+                tier_of(&b),
+                "{} and {} are in different tiers",
+                a,
+                b
+            );
+            let winner = a.clone().min(b.clone());
+            strategy.record_prior(&a, &b, &winner).unwrap();
+            asked += 1;
+        }
+        // Every pair within a tier
+        assert_eq!(asked, 18);
+    }
+
+    #[test]
+    fn test_known_pairs_leave_target_for_close_scores() {
+        use crate::score::ScoreSheet;
+
+        // Ten items scored in pairs: the 40 pairs decided by score alone
+        // would pass the target of 10 log2(10) = 34
+        let ids: Vec<Id> = create_test_items(10).into_iter().map(|i| i.id).collect();
+        let mut sheet = ScoreSheet::new(1);
+        for (i, id) in ids.iter().enumerate() {
+            sheet.set(id.clone(), 10 - 2 * (i / 2) as u8).unwrap();
+        }
+
+        let mut strategy = EloStrategy::new(ids.clone());
+        for (winner, loser) in sheet.implied_pairs() {
+            strategy.record_known(&winner, &loser, &winner).unwrap();
+        }
+
+        let mut asked = Vec::new();
+        while let Some((a, b)) = strategy.next_comparison() {
+            strategy.record_prior(&a, &b, &a).unwrap();
+            asked.push((a, b));
+        }
+        // Only the five pairs with equal scores
+        assert_eq!(asked.len(), 5);
+        assert!(asked
+            .iter()
+            .all(|(a, b)| sheet.scores[a] == sheet.scores[b]));
+    }
+
+    #[test]
+    fn test_warm_start_sets_initial_ratings() {
+        let items = create_test_items(5);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let mut strategy = EloStrategy::new(ids.clone());
+
+        // Prior order is the reverse, and one item is missing from it
+        let hint = vec![
+            ids[4].clone(),
+            ids[3].clone(),
+            ids[2].clone(),
+            ids[0].clone(),
+        ];
+        strategy.warm_start(&hint);
+
+        assert!(strategy.rating(&ids[4]) > strategy.rating(&ids[3]));
+        assert!(strategy.rating(&ids[3]) > strategy.rating(&ids[2]));
+        assert!(strategy.rating(&ids[0]) > strategy.rating(&ids[1]));
+
+        // The first pair confirms neighbours in the prior order
+        assert_eq!(
+            strategy.next_comparison(),
+            Some((ids[0].clone(), ids[1].clone()))
+        );
+    }
+
+    #[test]
+    fn test_serialize_deserialize() {
+        let items = create_test_items(4);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+        let mut strategy = EloStrategy::new(ids.clone());
+
+        let (a, b) = strategy.next_comparison().unwrap();
+        strategy.record_prior(&a, &b, &a).unwrap();
+
+        let state = strategy.serialize_state().unwrap();
+        let mut new_strategy = EloStrategy::new(ids);
+        new_strategy.deserialize_state(state).unwrap();
+
+        assert_eq!(strategy.next_comparison(), new_strategy.next_comparison());
+        assert_eq!(strategy.rating(&a), new_strategy.rating(&a));
+    }
+}
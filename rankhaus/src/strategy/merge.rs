@@ -5,8 +5,11 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Version of the merging rules, recorded with each ranking
+pub const VERSION: u32 = 1;
+
 /// Merge sort based ranking strategy
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MergeStrategy {
     items: Vec<Id>,
     comparisons: HashMap<(String, String), String>,
@@ -34,6 +37,10 @@ struct MergeOp {
     left_source: Option<usize>,
     /// Index of the merge operation that produces the right input (if any)
     right_source: Option<usize>,
+    /// Compare the last of left with the first of right before merging,
+    /// so inputs that are already in order are joined with one comparison
+    #[serde(default)]
+    check_boundary: bool,
 }
 
 impl MergeStrategy {
@@ -86,6 +93,7 @@ impl MergeStrategy {
                         result: Vec::new(),
                         left_source: sublist_sources[i],
                         right_source: sublist_sources[i + 1],
+                        check_boundary: false,
                     };
                     self.state.merge_stack.push(op);
 
@@ -143,6 +151,40 @@ impl RankStrategy for MergeStrategy {
         Ok(())
     }
 
+    fn record_prior(&mut self, a: &Id, b: &Id, winner_id: &Id) -> Result<()> {
+        if !self.items.contains(a) || !self.items.contains(b) {
+            return Ok(());
+        }
+
+        let key = self.get_comparison_key(a, b);
+        self.comparisons.insert(key, winner_id.to_string());
+        self.process_merges();
+
+        Ok(())
+    }
+
+    fn warm_start(&mut self, order: &[Id]) {
+        if self.items.len() < 2 {
+            return;
+        }
+
+        // Lay the items out in the prior order, so each merge can first
+        // check whether its inputs are already in order
+        let position = |id: &Id| order.iter().position(|o| o == id).unwrap_or(usize::MAX);
+        self.items.sort_by_key(position);
+
+        self.state = MergeState {
+            merge_stack: Vec::new(),
+            sorted: Vec::new(),
+            completed: false,
+        };
+        self.initialize_merge_sort();
+        for op in &mut self.state.merge_stack {
+            op.check_boundary = true;
+        }
+        self.process_merges();
+    }
+
     fn finalize(&mut self) -> Result<RankResult> {
         if !self.state.completed {
             return Err(crate::Error::Other("Ranking not complete".to_string()));
@@ -151,9 +193,20 @@ impl RankStrategy for MergeStrategy {
         Ok(RankResult {
             order: Some(self.state.sorted.clone()),
             ratings: None,
+            tiers: None,
+            scores: None,
+            groups: None,
+            confidence: None,
         })
     }
 
+    fn finalize_best_effort(&mut self) -> Result<RankResult> {
+        if self.state.completed {
+            return self.finalize();
+        }
+        super::complete_by_guessing(self.clone(), &self.comparisons)
+    }
+
     fn serialize_state(&self) -> Result<serde_json::Value> {
         Ok(serde_json::to_value(&self.state)?)
     }
@@ -166,6 +219,19 @@ impl RankStrategy for MergeStrategy {
     fn next_comparison(&self) -> Option<(Id, Id)> {
         // Find the next pair that needs comparison
         for op in &self.state.merge_stack {
+            // Inputs still waiting on an earlier merge aren't sorted yet
+            if op.left_source.is_some() || op.right_source.is_some() {
+                continue;
+            }
+
+            if op.check_boundary && op.left_idx == 0 && op.right_idx == 0 {
+                if let (Some(last), Some(first)) = (op.left.last(), op.right.first()) {
+                    if self.get_winner(last, first).is_none() {
+                        return Some((last.clone(), first.clone()));
+                    }
+                }
+            }
+
             if op.left_idx < op.left.len() && op.right_idx < op.right.len() {
                 let left_item = &op.left[op.left_idx];
                 let right_item = &op.right[op.right_idx];
@@ -186,7 +252,33 @@ impl RankStrategy for MergeStrategy {
 }
 
 impl MergeStrategy {
+    /// Apply cached comparisons until no merge can make further progress,
+    /// so completed merges feed their parents within the same call
     fn process_merges(&mut self) {
+        loop {
+            let before = self.progress();
+            self.process_merges_pass();
+            if self.progress() == before {
+                break;
+            }
+        }
+    }
+
+    /// Number of merged elements and resolved inputs across all operations
+    fn progress(&self) -> usize {
+        self.state
+            .merge_stack
+            .iter()
+            .map(|op| {
+                op.left_idx
+                    + op.right_idx
+                    + usize::from(op.left_source.is_none())
+                    + usize::from(op.right_source.is_none())
+            })
+            .sum()
+    }
+
+    fn process_merges_pass(&mut self) {
         let comparisons = &self.comparisons;
         let mut completed_ops = Vec::new();
 
@@ -243,6 +335,22 @@ impl MergeStrategy {
                 continue;
             }
 
+            if op.check_boundary && op.left_idx == 0 && op.right_idx == 0 {
+                if let (Some(last), Some(first)) = (op.left.last(), op.right.first()) {
+                    let key = Self::make_comparison_key(last, first);
+                    match comparisons.get(&key) {
+                        // Left already precedes right, join them as-is
+                        Some(winner) if winner == &last.to_string() => {
+                            op.result = op.left.iter().chain(&op.right).cloned().collect();
+                            op.left_idx = op.left.len();
+                            op.right_idx = op.right.len();
+                        }
+                        Some(_) => op.check_boundary = false,
+                        None => continue,
+                    }
+                }
+            }
+
             let mut made_progress = true;
 
             while made_progress && op.left_idx < op.left.len() && op.right_idx < op.right.len() {
@@ -527,6 +635,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_reverse_preference() {
+        let items = create_test_items(8);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        let mut strategy = MergeStrategy::new(ids.clone());
+
+        let mut comparison_count = 0;
+        while let Some((a_id, b_id)) = strategy.next_comparison() {
+            let a = items.iter().find(|item| item.id == a_id).unwrap();
+            let b = items.iter().find(|item| item.id == b_id).unwrap();
+
+            // Prefer the later item, so every merge has to reorder its inputs
+            let winner = if a.value > b.value { a } else { b };
+            strategy.compare(a, b, &winner.id).unwrap();
+
+            comparison_count += 1;
+        }
+
+        assert!(comparison_count <= 17); // Merge sort worst case for 8 items
+
+        let order = strategy.finalize().unwrap().order.unwrap();
+        let expected: Vec<Id> = ids.into_iter().rev().collect();
+        assert_eq!(order, expected);
+    }
+
+    #[test]
+    fn test_record_prior() {
+        let items = create_test_items(5);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // Answers from elsewhere, including a pair this strategy may never ask
+        let mut strategy = MergeStrategy::new(ids.clone());
+        strategy.record_prior(&ids[0], &ids[4], &ids[0]).unwrap();
+        strategy.record_prior(&ids[0], &ids[1], &ids[0]).unwrap();
+        strategy
+            .record_prior(&ids[2], &Id::from("unknown"), &ids[2])
+            .unwrap();
+
+        while let Some((a_id, b_id)) = strategy.next_comparison() {
+            let pair = [a_id.clone(), b_id.clone()];
+            assert!(!(pair.contains(&ids[0]) && pair.contains(&ids[1])));
+
+            let a = items.iter().find(|item| item.id == a_id).unwrap();
+            let b = items.iter().find(|item| item.id == b_id).unwrap();
+            let winner = if a.value < b.value { a } else { b };
+            strategy.compare(a, b, &winner.id).unwrap();
+        }
+
+        assert_eq!(strategy.finalize().unwrap().order.unwrap(), ids);
+    }
+
+    #[test]
+    fn test_warm_start_confirms_prior_order() {
+        let items = create_test_items(8);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // Hint matches the preference exactly, starting from a shuffled list
+        let shuffled = vec![
+            ids[5].clone(),
+            ids[2].clone(),
+            ids[7].clone(),
+            ids[0].clone(),
+            ids[3].clone(),
+            ids[6].clone(),
+            ids[1].clone(),
+            ids[4].clone(),
+        ];
+        let mut strategy = MergeStrategy::new(shuffled);
+        strategy.warm_start(&ids);
+
+        let mut comparison_count = 0;
+        while let Some((a_id, b_id)) = strategy.next_comparison() {
+            let a = items.iter().find(|item| item.id == a_id).unwrap();
+            let b = items.iter().find(|item| item.id == b_id).unwrap();
+            let winner = if a.value < b.value { a } else { b };
+            strategy.compare(a, b, &winner.id).unwrap();
+            comparison_count += 1;
+        }
+
+        assert_eq!(comparison_count, 7); // One confirmation per merge
+        assert_eq!(strategy.finalize().unwrap().order.unwrap(), ids);
+    }
+
+    #[test]
+    fn test_warm_start_with_changed_preference() {
+        let items = create_test_items(6);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // Hint has the first and last items swapped
+        let mut hint = ids.clone();
+        hint.swap(0, 5);
+
+        let mut strategy = MergeStrategy::new(ids.clone());
+        strategy.warm_start(&hint);
+
+        while let Some((a_id, b_id)) = strategy.next_comparison() {
+            let a = items.iter().find(|item| item.id == a_id).unwrap();
+            let b = items.iter().find(|item| item.id == b_id).unwrap();
+            let winner = if a.value < b.value { a } else { b };
+            strategy.compare(a, b, &winner.id).unwrap();
+        }
+
+        assert_eq!(strategy.finalize().unwrap().order.unwrap(), ids);
+    }
+
     #[test]
     fn test_serialize_deserialize() {
         let items = create_test_items(3);
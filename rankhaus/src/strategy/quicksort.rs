@@ -1,14 +1,72 @@
 use crate::{
+    seed::{self, Stream},
     strategy::{RankResult, RankStrategy},
     Id, Item, Result,
 };
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Version of the partitioning rules, recorded with each ranking
+pub const VERSION: u32 = 1;
+
+/// How quicksort picks the pivot of each partition
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PivotRule {
+    /// A random item, drawn from the ranking's seed
+    #[default]
+    Random,
+    /// The middle item
+    Middle,
+}
+
+/// Options for the quicksort strategy
+///
+/// A warm start always picks the median by prior rank, whatever the rule.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuickSortConfig {
+    pub pivot: PivotRule,
+}
+
+impl QuickSortConfig {
+    pub const OPTIONS: &'static [&'static str] = &["pivot"];
+
+    /// Set an option from its text value
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "pivot" => {
+                self.pivot = match value.trim().to_lowercase().as_str() {
+                    "random" => PivotRule::Random,
+                    "middle" => PivotRule::Middle,
+                    _ => {
+                        return Err(crate::Error::Other(
+                            "pivot must be 'random' or 'middle'".to_string(),
+                        ))
+                    }
+                }
+            }
+            _ => return Err(super::unknown_option("quicksort", key, Self::OPTIONS)),
+        }
+        Ok(())
+    }
+
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        let pivot = match self.pivot {
+            PivotRule::Random => "random",
+            PivotRule::Middle => "middle",
+        };
+        vec![("pivot", pivot.to_string())]
+    }
+}
+
 /// QuickSort based ranking strategy
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QuickSortStrategy {
     items: Vec<Id>,
+    #[serde(default)]
+    config: QuickSortConfig,
     comparisons: HashMap<(String, String), String>,
     state: QuickSortState,
 }
@@ -21,6 +79,12 @@ struct QuickSortState {
     sorted: Vec<Id>,
     /// Whether the sort is complete
     completed: bool,
+    /// Position of each item in a prior ranking, used to pick pivots
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    hint: HashMap<Id, usize>,
+    /// Seed for random pivots; without one the middle item is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,13 +105,20 @@ struct PartitionOp {
 
 impl QuickSortStrategy {
     pub fn new(items: Vec<Id>) -> Self {
+        Self::with_config(items, QuickSortConfig::default())
+    }
+
+    pub fn with_config(items: Vec<Id>, config: QuickSortConfig) -> Self {
         let mut strategy = Self {
             items: items.clone(),
+            config,
             comparisons: HashMap::new(),
             state: QuickSortState {
                 partition_stack: Vec::new(),
                 sorted: Vec::new(),
                 completed: false,
+                hint: HashMap::new(),
+                seed: None,
             },
         };
 
@@ -68,7 +139,7 @@ impl QuickSortStrategy {
         // Start with the full list
         let op = PartitionOp {
             items: self.items.clone(),
-            pivot_idx: self.items.len() / 2, // Middle element as pivot
+            pivot_idx: self.choose_pivot(&self.items, 0),
             less: Vec::new(),
             greater: Vec::new(),
             current_idx: 0,
@@ -77,16 +148,85 @@ impl QuickSortStrategy {
         self.state.partition_stack.push(op);
     }
 
+    /// Median by prior rank when warm started, otherwise a random element
+    /// when seeded or the middle one
+    ///
+    /// Random pivots are drawn per partition, identified by where it starts
+    /// and its size, so replaying answers picks the same pivots.
+    fn choose_pivot(&self, items: &[Id], start_pos: usize) -> usize {
+        if self.state.hint.is_empty() {
+            return match self.state.seed {
+                Some(seed) => {
+                    let index = ((start_pos as u64) << 32) | items.len() as u64;
+                    seed::rng(seed, Stream::Strategy, index).random_range(0..items.len())
+                }
+                None => items.len() / 2,
+            };
+        }
+
+        let mut by_hint: Vec<usize> = (0..items.len()).collect();
+        by_hint.sort_by_key(|&i| {
+            let position = self.state.hint.get(&items[i]).copied();
+            (position.unwrap_or(usize::MAX), i)
+        });
+        by_hint[items.len() / 2]
+    }
+
     fn get_comparison(&self, a: &Id, b: &Id) -> Option<Id> {
+        Self::lookup(&self.comparisons, a, b)
+    }
+
+    fn lookup(comparisons: &HashMap<(String, String), String>, a: &Id, b: &Id) -> Option<Id> {
         let key1 = (a.to_string(), b.to_string());
         let key2 = (b.to_string(), a.to_string());
 
-        self.comparisons
+        comparisons
             .get(&key1)
-            .or_else(|| self.comparisons.get(&key2))
+            .or_else(|| comparisons.get(&key2))
             .map(|s| Id::from(s.as_str()))
     }
 
+    /// Partition items using cached answers, finishing partitions as they
+    /// complete, until a pair without an answer is reached
+    fn advance(&mut self) {
+        while let Some(op) = self.state.partition_stack.last_mut() {
+            while op.current_idx < op.items.len() {
+                // Skip pivot itself
+                if op.current_idx == op.pivot_idx {
+                    op.current_idx += 1;
+                    continue;
+                }
+
+                let current = op.items[op.current_idx].clone();
+                let pivot = &op.items[op.pivot_idx];
+
+                match Self::lookup(&self.comparisons, &current, pivot) {
+                    // Current is better (less) than pivot
+                    Some(winner) if winner == current => op.less.push(current),
+                    // Pivot is better, current goes to greater
+                    Some(_) => op.greater.push(current),
+                    None => return,
+                }
+                op.current_idx += 1;
+            }
+
+            self.process_partition();
+        }
+
+        self.state.completed = true;
+    }
+
+    /// Re-pick the first pivot if partitioning hasn't started yet
+    fn repick_first_pivot(&mut self) {
+        if let [op] = self.state.partition_stack.as_slice() {
+            if op.current_idx == 0 && op.less.is_empty() && op.greater.is_empty() {
+                let pivot_idx = self.choose_pivot(&op.items, 0);
+                self.state.partition_stack[0].pivot_idx = pivot_idx;
+                self.advance();
+            }
+        }
+    }
+
     fn process_partition(&mut self) -> bool {
         if self.state.partition_stack.is_empty() {
             return false;
@@ -137,7 +277,7 @@ impl QuickSortStrategy {
             if op.greater.len() > 1 {
                 let greater_op = PartitionOp {
                     items: op.greater.clone(),
-                    pivot_idx: op.greater.len() / 2,
+                    pivot_idx: self.choose_pivot(&op.greater, pivot_pos + 1),
                     less: Vec::new(),
                     greater: Vec::new(),
                     current_idx: 0,
@@ -149,7 +289,7 @@ impl QuickSortStrategy {
             if op.less.len() > 1 {
                 let less_op = PartitionOp {
                     items: op.less.clone(),
-                    pivot_idx: op.less.len() / 2,
+                    pivot_idx: self.choose_pivot(&op.less, op.start_pos),
                     less: Vec::new(),
                     greater: Vec::new(),
                     current_idx: 0,
@@ -258,6 +398,34 @@ impl RankStrategy for QuickSortStrategy {
         Ok(())
     }
 
+    fn record_prior(&mut self, a: &Id, b: &Id, winner_id: &Id) -> Result<()> {
+        if !self.items.contains(a) || !self.items.contains(b) {
+            return Ok(());
+        }
+
+        let key = (a.to_string(), b.to_string());
+        self.comparisons.insert(key, winner_id.to_string());
+        self.advance();
+
+        Ok(())
+    }
+
+    fn seed(&mut self, seed: u64) {
+        if self.config.pivot == PivotRule::Random {
+            self.state.seed = Some(seed);
+            self.repick_first_pivot();
+        }
+    }
+
+    fn warm_start(&mut self, order: &[Id]) {
+        self.state.hint = order
+            .iter()
+            .enumerate()
+            .map(|(position, id)| (id.clone(), position))
+            .collect();
+        self.repick_first_pivot();
+    }
+
     fn finalize(&mut self) -> Result<RankResult> {
         if !self.state.completed {
             return Err(crate::Error::Other(
@@ -268,9 +436,20 @@ impl RankStrategy for QuickSortStrategy {
         Ok(RankResult {
             order: Some(self.state.sorted.clone()),
             ratings: None,
+            tiers: None,
+            scores: None,
+            groups: None,
+            confidence: None,
         })
     }
 
+    fn finalize_best_effort(&mut self) -> Result<RankResult> {
+        if self.state.completed {
+            return self.finalize();
+        }
+        super::complete_by_guessing(self.clone(), &self.comparisons)
+    }
+
     fn serialize_state(&self) -> Result<serde_json::Value> {
         Ok(serde_json::to_value(&self.state)?)
     }
@@ -326,10 +505,12 @@ mod tests {
 
     fn create_test_items(count: usize) -> Vec<Item> {
         (0..count)
-            .map(|i| Item {
-                id: Id::from(format!("i{}", i)),
-                value: format!("Item {}", i),
-                created: chrono::Utc::now(),
+            .map(|i| {
+                Item::with_id(
+                    Id::from(format!("i{}", i)),
+                    format!("Item {}", i),
+                    chrono::Utc::now(),
+                )
             })
             .collect()
     }
@@ -423,6 +604,72 @@ mod tests {
         assert_eq!(strategy.is_complete(), new_strategy.is_complete());
     }
 
+    #[test]
+    fn test_record_prior_replay() {
+        let items = create_test_items(7);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // Answer a few comparisons, then replay them into a fresh strategy
+        let mut strategy = QuickSortStrategy::new(ids.clone());
+        let mut answered = Vec::new();
+        for _ in 0..4 {
+            let (a, b) = strategy.next_comparison().unwrap();
+            let winner = if a.as_str() < b.as_str() {
+                a.clone()
+            } else {
+                b.clone()
+            };
+            strategy.record_prior(&a, &b, &winner).unwrap();
+            answered.push((a, b, winner));
+        }
+
+        let mut replayed = QuickSortStrategy::new(ids.clone());
+        for (a, b, winner) in &answered {
+            replayed.record_prior(a, b, winner).unwrap();
+        }
+
+        assert_eq!(strategy.next_comparison(), replayed.next_comparison());
+    }
+
+    #[test]
+    fn test_record_prior_from_other_order() {
+        let items = create_test_items(6);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // A full set of answers in arbitrary order completes the ranking
+        let mut strategy = QuickSortStrategy::new(ids.clone());
+        for i in (0..ids.len()).rev() {
+            for j in 0..i {
+                strategy.record_prior(&ids[i], &ids[j], &ids[j]).unwrap();
+            }
+        }
+
+        assert!(strategy.is_complete());
+        assert!(strategy.next_comparison().is_none());
+        assert_eq!(strategy.finalize().unwrap().order.unwrap(), ids);
+    }
+
+    #[test]
+    fn test_warm_start_picks_median_pivot() {
+        let items = create_test_items(7);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // Prior order is the reverse of the item list
+        let hint: Vec<Id> = ids.iter().rev().cloned().collect();
+        let mut strategy = QuickSortStrategy::new(ids.clone());
+        strategy.warm_start(&hint);
+
+        let (_, pivot) = strategy.next_comparison().unwrap();
+        assert_eq!(pivot, hint[3]);
+
+        while let Some((a, b)) = strategy.next_comparison() {
+            let winner = if a.as_str() > b.as_str() { &a } else { &b };
+            strategy.record_prior(&a, &b, winner).unwrap();
+        }
+
+        assert_eq!(strategy.finalize().unwrap().order.unwrap(), hint);
+    }
+
     #[test]
     fn test_finalize_before_complete() {
         let items = create_test_items(3);
@@ -434,6 +681,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::nonminimal_bool)]
     fn test_no_duplicate_comparisons() {
         let items = create_test_items(9);
         let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
@@ -477,4 +725,31 @@ mod tests {
             comparisons.len()
         );
     }
+
+    #[test]
+    fn test_seeded_pivots() {
+        let items = create_test_items(9);
+        let ids: Vec<Id> = items.iter().map(|item| item.id.clone()).collect();
+
+        // Lower index wins; returns the pairs asked and the final order
+        let run = |seed: Option<u64>| {
+            let mut strategy = QuickSortStrategy::new(ids.clone());
+            if let Some(seed) = seed {
+                strategy.seed(seed);
+            }
+            let mut asked = Vec::new();
+            while let Some((a, b)) = strategy.next_comparison() {
+                let winner = a.clone().min(b.clone());
+                strategy.record_prior(&a, &b, &winner).unwrap();
+                asked.push((a, b));
+            }
+            (asked, strategy.finalize().unwrap().order.unwrap())
+        };
+
+        let (unseeded, order) = run(None);
+        assert_eq!(order, ids);
+        assert_eq!(run(Some(5)), run(Some(5)));
+        assert_eq!(run(Some(5)).1, ids);
+        assert!((0..10).any(|seed| run(Some(seed)).0 != unseeded));
+    }
 }
@@ -0,0 +1,222 @@
+//! Conformance checks for ranking strategies
+//!
+//! Any [`RankStrategy`] should pass these, including ones written outside
+//! this crate. Enable the `testkit` feature and run them from a test:
+//!
+//! ```ignore
+//! #[test]
+//! fn my_strategy_conforms() {
+//!     Conformance::new(|items| Box::new(MyStrategy::new(items))).assert_conforms();
+//! }
+//! ```
+//!
+//! Each case ranks a random number of items against a ranker that answers
+//! from a random true order, and checks that the strategy:
+//! - finishes, asking no more than every pair once,
+//! - never asks about an item against itself or repeats an answered pair,
+//! - returns a permutation of the items it was given,
+//! - agrees with the true order,
+//! - carries on exactly as before after its state is serialized partway
+//!   through and restored into a fresh instance.
+
+use crate::correlation;
+use crate::{Id, Item, RankStrategy};
+use proptest::prelude::*;
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+use std::collections::{HashMap, HashSet};
+
+/// Conformance checks for one strategy
+pub struct Conformance<F> {
+    factory: F,
+    max_items: usize,
+    cases: u32,
+    min_tau: Option<f64>,
+}
+
+impl<F> Conformance<F>
+where
+    F: Fn(Vec<Id>) -> Box<dyn RankStrategy>,
+{
+    /// Check the strategies `factory` creates for a list of items
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            max_items: 10,
+            cases: 64,
+            min_tau: None,
+        }
+    }
+
+    /// Largest number of items to rank (default 10)
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items;
+        self
+    }
+
+    /// Number of random cases to run (default 64)
+    pub fn cases(mut self, cases: u32) -> Self {
+        self.cases = cases;
+        self
+    }
+
+    /// Accept results that only approximate the true order
+    ///
+    /// By default the result must match it exactly. Rating strategies
+    /// that stop before every pair is settled can instead require a
+    /// Kendall's tau of at least `min_tau` against it.
+    pub fn approximate(mut self, min_tau: f64) -> Self {
+        self.min_tau = Some(min_tau);
+        self
+    }
+
+    /// Run every check, panicking with the smallest failing case
+    pub fn assert_conforms(&self) {
+        if let Err(message) = self.run() {
+            panic!("{}", message);
+        }
+    }
+
+    /// Run every check over random cases
+    pub fn run(&self) -> Result<(), String> {
+        let mut runner = TestRunner::new(Config::with_cases(self.cases));
+        let cases = (0..=self.max_items).prop_flat_map(|n| {
+            let items: Vec<Id> = (0..n).map(|i| Id::from(format!("i{:03}", i))).collect();
+            (Just(items).prop_shuffle(), 0..=n * n)
+        });
+
+        runner
+            .run(&cases, |(truth, pause_after)| {
+                self.check(&truth, pause_after).map_err(TestCaseError::fail)
+            })
+            .map_err(|e| e.to_string())
+    }
+
+    /// Check one case
+    ///
+    /// `truth` lists the items best first. The strategy is given them in ID
+    /// order, and its state is round-tripped after `pause_after` answers
+    /// (or never, if it finishes sooner).
+    pub fn check(&self, truth: &[Id], pause_after: usize) -> Result<(), String> {
+        let mut input = truth.to_vec();
+        input.sort();
+
+        let position: HashMap<&Id, usize> =
+            truth.iter().enumerate().map(|(i, id)| (id, i)).collect();
+        let items: HashMap<Id, Item> = input
+            .iter()
+            .map(|id| {
+                let item = Item::with_id(id.clone(), id.to_string(), chrono::Utc::now());
+                (id.clone(), item)
+            })
+            .collect();
+
+        let mut strategy = (self.factory)(input.clone());
+        // Only set once the state has been round-tripped
+        let mut restored: Option<Box<dyn RankStrategy>> = None;
+        let mut asked: HashSet<(Id, Id)> = HashSet::new();
+        let n = input.len();
+        let all_pairs = n * n.saturating_sub(1) / 2;
+
+        loop {
+            if asked.len() == pause_after && restored.is_none() {
+                let state = strategy.serialize_state().map_err(|e| e.to_string())?;
+                let mut fresh = (self.factory)(input.clone());
+                fresh
+                    .deserialize_state(state)
+                    .map_err(|e| format!("Restoring state failed: {}", e))?;
+                restored = Some(fresh);
+            }
+
+            let next = strategy.next_comparison();
+            if let Some(restored) = &restored {
+                if restored.next_comparison() != next {
+                    return Err(format!(
+                        "After restoring state at {} answers, asked {:?} instead of {:?}",
+                        pause_after,
+                        restored.next_comparison(),
+                        next
+                    ));
+                }
+            }
+
+            let Some((a, b)) = next else { break };
+            if a == b {
+                return Err(format!("Asked to compare {} with itself", a));
+            }
+            let (Some(item_a), Some(item_b)) = (items.get(&a), items.get(&b)) else {
+                return Err(format!("Asked about unknown items {} and {}", a, b));
+            };
+            let pair = if a < b {
+                (a.clone(), b.clone())
+            } else {
+                (b.clone(), a.clone())
+            };
+            if !asked.insert(pair) {
+                return Err(format!("Asked about {} and {} again", a, b));
+            }
+            if asked.len() > all_pairs {
+                return Err(format!("Asked more than all {} pairs", all_pairs));
+            }
+
+            let winner = if position[&a] < position[&b] { &a } else { &b };
+            strategy
+                .compare(item_a, item_b, winner)
+                .map_err(|e| e.to_string())?;
+            if let Some(restored) = &mut restored {
+                restored
+                    .compare(item_a, item_b, winner)
+                    .map_err(|e| format!("Restored strategy: {}", e))?;
+            }
+        }
+
+        if !strategy.is_complete() {
+            return Err(format!(
+                "Stopped asking after {} answers without completing",
+                asked.len()
+            ));
+        }
+
+        let order = strategy
+            .finalize()
+            .map_err(|e| e.to_string())?
+            .order
+            .unwrap_or_default();
+        if let Some(restored) = &mut restored {
+            let restored_order = restored
+                .finalize()
+                .map_err(|e| format!("Restored strategy: {}", e))?
+                .order
+                .unwrap_or_default();
+            if restored_order != order {
+                return Err(format!(
+                    "Restored strategy finished with {:?} instead of {:?}",
+                    restored_order, order
+                ));
+            }
+        }
+
+        let mut sorted = order.clone();
+        sorted.sort();
+        if sorted != input {
+            return Err(format!(
+                "Result {:?} is not a permutation of {:?}",
+                order, input
+            ));
+        }
+
+        match self.min_tau {
+            None if order != truth => Err(format!(
+                "Result {:?} does not match the true order {:?}",
+                order, truth
+            )),
+            Some(min_tau) if correlation::kendall_tau(&order, truth) < min_tau => Err(format!(
+                "Result {:?} has Kendall's tau {:.2} against the true order {:?}, below {}",
+                order,
+                correlation::kendall_tau(&order, truth),
+                truth,
+                min_tau
+            )),
+            _ => Ok(()),
+        }
+    }
+}
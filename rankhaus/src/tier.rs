@@ -0,0 +1,240 @@
+//! Tier list ranking
+//!
+//! Items are dropped into labelled buckets, best tier first. Every item in a
+//! better tier beats every item in a worse one, so ordering within tiers only
+//! needs the comparisons between items that share a tier.
+
+use crate::{Error, Id, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Tier labels used when none are given, best first
+pub const DEFAULT_LABELS: [&str; 5] = ["S", "A", "B", "C", "D"];
+
+/// A labelled bucket of items in a finished tier list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tier {
+    pub label: String,
+    /// Item IDs, best to worst if the tier was ordered
+    pub items: Vec<Id>,
+}
+
+/// Tier list settings and the assignments made so far
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TierList {
+    /// Tier labels, best first
+    pub labels: Vec<String>,
+    /// Order items within each tier with the ranking's strategy
+    #[serde(default)]
+    pub order_within: bool,
+    /// Tier index for each item assigned so far
+    #[serde(default)]
+    pub assignments: BTreeMap<Id, usize>,
+}
+
+impl TierList {
+    /// Create a tier list with the given labels, best first
+    pub fn new(labels: Vec<String>, order_within: bool) -> Result<Self> {
+        if labels.is_empty() {
+            return Err(Error::Other(
+                "A tier list needs at least one tier".to_string(),
+            ));
+        }
+
+        let mut seen = BTreeSet::new();
+        for label in &labels {
+            if label.trim().is_empty() {
+                return Err(Error::Other("Tier labels can't be empty".to_string()));
+            }
+            if !seen.insert(label.to_lowercase()) {
+                return Err(Error::Other(format!("Duplicate tier label: {}", label)));
+            }
+        }
+
+        Ok(Self {
+            labels,
+            order_within,
+            assignments: BTreeMap::new(),
+        })
+    }
+
+    /// Find a tier by label (case insensitive) or by its 1-based number
+    pub fn find_tier(&self, input: &str) -> Option<usize> {
+        let input = input.trim();
+        self.labels
+            .iter()
+            .position(|label| label.eq_ignore_ascii_case(input))
+            .or_else(|| {
+                input
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| (1..=self.labels.len()).contains(n))
+                    .map(|n| n - 1)
+            })
+    }
+
+    /// Put an item into a tier
+    pub fn assign(&mut self, item: Id, tier: usize) -> Result<()> {
+        if tier >= self.labels.len() {
+            return Err(Error::Other(format!("No tier at index {}", tier)));
+        }
+        self.assignments.insert(item, tier);
+        Ok(())
+    }
+
+    /// Items from `items` that have not been put into a tier yet
+    pub fn unassigned(&self, items: &[Id]) -> Vec<Id> {
+        items
+            .iter()
+            .filter(|id| !self.assignments.contains_key(id))
+            .cloned()
+            .collect()
+    }
+
+    /// A (winner, loser) pair from `known` that putting `item` into `tier`
+    /// would contradict, because the other item is already in a tier on the
+    /// wrong side
+    pub fn conflict<'a>(
+        &self,
+        item: &Id,
+        tier: usize,
+        known: &'a BTreeSet<(Id, Id)>,
+    ) -> Option<&'a (Id, Id)> {
+        known.iter().find(|(winner, loser)| {
+            if winner == item {
+                self.assignments.get(loser).is_some_and(|&t| t < tier)
+            } else if loser == item {
+                self.assignments.get(winner).is_some_and(|&t| t > tier)
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Every (winner, loser) pair decided by the tiers alone
+    pub fn implied_pairs(&self) -> Vec<(Id, Id)> {
+        let mut pairs = Vec::new();
+        for (a, tier_a) in &self.assignments {
+            for (b, tier_b) in &self.assignments {
+                if tier_a < tier_b {
+                    pairs.push((a.clone(), b.clone()));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Assigned items grouped by tier, keeping their relative order in
+    /// `order`; items not in `order` follow in ID order
+    pub fn tiers(&self, order: &[Id]) -> Vec<Tier> {
+        let mut tiers: Vec<Tier> = self
+            .labels
+            .iter()
+            .map(|label| Tier {
+                label: label.clone(),
+                items: Vec::new(),
+            })
+            .collect();
+
+        let listed: BTreeSet<&Id> = order.iter().collect();
+        let rest = self.assignments.keys().filter(|id| !listed.contains(id));
+
+        for id in order.iter().chain(rest) {
+            if let Some(&tier) = self.assignments.get(id) {
+                tiers[tier].items.push(id.clone());
+            }
+        }
+
+        tiers
+    }
+
+    /// All assigned items, best tier first
+    pub fn order(&self, order: &[Id]) -> Vec<Id> {
+        self.tiers(order)
+            .into_iter()
+            .flat_map(|tier| tier.items)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_tiers() -> TierList {
+        TierList::new(
+            DEFAULT_LABELS.iter().map(|l| l.to_string()).collect(),
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_new_rejects_bad_labels() {
+        assert!(TierList::new(vec![], false).is_err());
+        assert!(TierList::new(vec!["S".to_string(), "s".to_string()], false).is_err());
+        assert!(TierList::new(vec!["S".to_string(), " ".to_string()], false).is_err());
+    }
+
+    #[test]
+    fn test_find_tier() {
+        let tiers = default_tiers();
+        assert_eq!(tiers.find_tier("s"), Some(0));
+        assert_eq!(tiers.find_tier("C"), Some(3));
+        assert_eq!(tiers.find_tier("2"), Some(1));
+        assert_eq!(tiers.find_tier("6"), None);
+        assert_eq!(tiers.find_tier("X"), None);
+    }
+
+    #[test]
+    fn test_implied_pairs_cross_tiers_only() {
+        let mut tiers = default_tiers();
+        tiers.assign(Id::from("a"), 0).unwrap();
+        tiers.assign(Id::from("b"), 0).unwrap();
+        tiers.assign(Id::from("c"), 2).unwrap();
+
+        let pairs = tiers.implied_pairs();
+        assert_eq!(pairs.len(), 2);
+        assert!(pairs.contains(&(Id::from("a"), Id::from("c"))));
+        assert!(pairs.contains(&(Id::from("b"), Id::from("c"))));
+    }
+
+    #[test]
+    fn test_conflict_with_known_pairs() {
+        let mut tiers = default_tiers();
+        tiers.assign(Id::from("b"), 1).unwrap();
+        let known: BTreeSet<(Id, Id)> = [(Id::from("a"), Id::from("b"))].into_iter().collect();
+
+        // a must be above b, so it can't go below b's tier
+        assert_eq!(
+            tiers.conflict(&Id::from("a"), 2, &known),
+            Some(&(Id::from("a"), Id::from("b")))
+        );
+        assert_eq!(tiers.conflict(&Id::from("a"), 1, &known), None);
+        assert_eq!(tiers.conflict(&Id::from("a"), 0, &known), None);
+        assert_eq!(tiers.conflict(&Id::from("c"), 4, &known), None);
+    }
+
+    #[test]
+    fn test_tiers_follow_order() {
+        let mut tiers = default_tiers();
+        tiers.assign(Id::from("a"), 1).unwrap();
+        tiers.assign(Id::from("b"), 0).unwrap();
+        tiers.assign(Id::from("c"), 1).unwrap();
+        tiers.assign(Id::from("d"), 4).unwrap();
+        assert!(tiers.assign(Id::from("e"), 5).is_err());
+
+        let order = [Id::from("b"), Id::from("c"), Id::from("a")];
+        let grouped = tiers.tiers(&order);
+        assert_eq!(grouped.len(), 5);
+        assert_eq!(grouped[0].items, vec![Id::from("b")]);
+        assert_eq!(grouped[1].items, vec![Id::from("c"), Id::from("a")]);
+        assert!(grouped[2].items.is_empty());
+        assert_eq!(grouped[4].items, vec![Id::from("d")]);
+
+        assert_eq!(
+            tiers.order(&[]),
+            vec![Id::from("b"), Id::from("a"), Id::from("c"), Id::from("d")]
+        );
+    }
+}
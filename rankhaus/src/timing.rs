@@ -0,0 +1,143 @@
+//! How long rankers take to answer
+//!
+//! Slow answers point at pairs that are genuinely hard to call, or at items
+//! whose description needs clarifying. Rating strategies can also be told to
+//! count slow answers as weak preferences.
+
+use crate::session::Comparison;
+use crate::Id;
+use serde::{Deserialize, Serialize};
+
+/// Weight of a slow answer when it counts as a weak preference
+pub const WEAK_PREFERENCE_WEIGHT: f64 = 0.5;
+
+/// Number of slowest pairs kept in a summary
+const SLOWEST_KEPT: usize = 3;
+
+/// A compared pair and how long it took to answer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimedPair {
+    pub a: Id,
+    pub b: Id,
+    pub latency_ms: u64,
+}
+
+/// Answer times over one or more sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencyStats {
+    /// Number of timed answers
+    pub count: usize,
+    pub total_ms: u64,
+    /// Median answer time; only known for a single session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub median_ms: Option<u64>,
+    /// Slowest pairs, slowest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub slowest: Vec<TimedPair>,
+}
+
+impl LatencyStats {
+    /// Summarize the comparisons that recorded an answer time
+    pub fn from_comparisons<'a>(comparisons: impl IntoIterator<Item = &'a Comparison>) -> Self {
+        let timed: Vec<TimedPair> = comparisons
+            .into_iter()
+            .filter_map(|c| {
+                c.latency_ms.map(|latency_ms| TimedPair {
+                    a: c.a.clone(),
+                    b: c.b.clone(),
+                    latency_ms,
+                })
+            })
+            .collect();
+
+        let mut times: Vec<u64> = timed.iter().map(|t| t.latency_ms).collect();
+        times.sort_unstable();
+        let median_ms = match times.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(times[n / 2]),
+            n => Some((times[n / 2 - 1] + times[n / 2]) / 2),
+        };
+
+        Self {
+            count: timed.len(),
+            total_ms: times.iter().sum(),
+            median_ms,
+            slowest: slowest(timed),
+        }
+    }
+
+    /// Combine stats from several sessions
+    ///
+    /// Medians can't be combined, so the result only has one when a single
+    /// session contributed.
+    pub fn combine(stats: impl IntoIterator<Item = LatencyStats>) -> Self {
+        let stats: Vec<LatencyStats> = stats.into_iter().filter(|s| s.count > 0).collect();
+        if stats.len() == 1 {
+            return stats.into_iter().next().unwrap_or_default();
+        }
+
+        Self {
+            count: stats.iter().map(|s| s.count).sum(),
+            total_ms: stats.iter().map(|s| s.total_ms).sum(),
+            median_ms: None,
+            slowest: slowest(stats.into_iter().flat_map(|s| s.slowest).collect()),
+        }
+    }
+
+    pub fn mean_ms(&self) -> Option<u64> {
+        (self.count > 0).then(|| self.total_ms / self.count as u64)
+    }
+}
+
+fn slowest(mut timed: Vec<TimedPair>) -> Vec<TimedPair> {
+    timed.sort_by_key(|t| std::cmp::Reverse(t.latency_ms));
+    timed.truncate(SLOWEST_KEPT);
+    timed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Session;
+
+    fn session(times: &[u64]) -> Session {
+        let mut session = Session::new();
+        for (i, ms) in times.iter().enumerate() {
+            let a = Id::from(format!("a{}", i));
+            let comparison = session.add_comparison(a.clone(), Id::from("b"), a);
+            comparison.latency_ms = Some(*ms);
+        }
+        session
+    }
+
+    #[test]
+    fn test_stats_from_comparisons() {
+        let mut session = session(&[4000, 1000, 2000, 9000]);
+        // Untimed answers are left out
+        session.add_comparison(Id::from("x"), Id::from("y"), Id::from("x"));
+
+        let stats = LatencyStats::from_comparisons(&session.comparisons);
+        assert_eq!(stats.count, 4);
+        assert_eq!(stats.mean_ms(), Some(4000));
+        assert_eq!(stats.median_ms, Some(3000));
+        assert_eq!(stats.slowest.len(), 3);
+        assert_eq!(stats.slowest[0].a, Id::from("a3"));
+        assert_eq!(stats.slowest[0].latency_ms, 9000);
+    }
+
+    #[test]
+    fn test_combine() {
+        let first = LatencyStats::from_comparisons(&session(&[1000, 3000]).comparisons);
+        let second = LatencyStats::from_comparisons(&session(&[5000]).comparisons);
+
+        let alone = LatencyStats::combine([first.clone(), LatencyStats::default()]);
+        assert_eq!(alone.median_ms, Some(2000));
+
+        let combined = LatencyStats::combine([first, second]);
+        assert_eq!(combined.count, 3);
+        assert_eq!(combined.mean_ms(), Some(3000));
+        assert_eq!(combined.median_ms, None);
+        assert_eq!(combined.slowest[0].latency_ms, 5000);
+        assert_eq!(LatencyStats::default().mean_ms(), None);
+    }
+}